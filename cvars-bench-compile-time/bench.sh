@@ -74,4 +74,11 @@ hyperfine --warmup 2 "echo '// test' >> src/main.rs && cargo build --features st
 #hyperfine --warmup 2 "echo '// test' >> src/main.rs && cargo build --features string,typed,derive-dummy,cvars-1000"
 #hyperfine --warmup 2 "echo '// test' >> src/main.rs && cargo build --features string,typed,derive-dummy,cvars-10000"
 
+# Compare matching on strings (above) with `#[cvars(lookup_table)]`.
+echo Lookup table:
+hyperfine --warmup 2 "sed --in-place 's/test0/test0a/' src/derive-100.in && cargo build --features string,typed,derive,lookup-table,cvars-100"
+hyperfine --warmup 2 "sed --in-place 's/test0/test0a/' src/derive-1000.in && cargo build --features string,typed,derive,cvars-1000"
+hyperfine --warmup 2 "sed --in-place 's/test0/test0a/' src/derive-1000.in && cargo build --features string,typed,derive,lookup-table,cvars-1000"
+#hyperfine --warmup 2 "sed --in-place 's/test0/test0a/' src/derive-10000.in && cargo build --features string,typed,derive,lookup-table,cvars-10000"
+
 ./cleanup.sh
@@ -3,6 +3,7 @@
 //   reduces the compile time by on nomacro/dummy 10k cvars by a bit over 10%
 //   but makes rustc crash when using the fast compiles configuration.
 // - Dummy 10k cvars: derive(Default) 703.1 ms, no Default 552.6 ms, manual new() impl 657.4 ms
+// - Derive 1k cvars, string+typed, editing cvars: matching on strings 8.7 s, lookup_table 6.2 s
 
 // LATER(perf) How to optimize and speed up everything?
 //  - Optional feature to generate cvars from build.rs to avoid running the macro every build?
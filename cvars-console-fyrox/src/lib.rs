@@ -1,6 +1,12 @@
 #![doc = include_str!("../README.md")]
 #![warn(missing_docs)]
 
+use std::{
+    any::Any,
+    io,
+    path::{Path, PathBuf},
+};
+
 use fyrox_ui::{
     border::BorderBuilder,
     brush::Brush,
@@ -17,6 +23,8 @@ use fyrox_ui::{
 use cvars::SetGet;
 use cvars_console::Console;
 
+pub use cvars_console::{default_history_path, Arg, Command, CommandHandler, Commands};
+
 /// In-game console for the Fyrox game engine.
 pub struct FyroxConsole {
     is_open: bool,
@@ -154,6 +162,12 @@ impl FyroxConsole {
                 self.console.history_forward();
                 self.update_ui_prompt(ui);
             }
+            Some(WidgetMessage::KeyDown(KeyCode::Tab)) => {
+                // Complete at the end, the console doesn't know where the text box's caret is.
+                self.console.complete_prompt(cvars);
+                self.update_ui_prompt(ui);
+                self.update_ui_history(ui);
+            }
             Some(WidgetMessage::KeyDown(KeyCode::PageUp)) => {
                 self.console.history_scroll_up(10);
                 self.update_ui_history(ui);
@@ -163,7 +177,7 @@ impl FyroxConsole {
                 self.update_ui_history(ui);
             }
             Some(WidgetMessage::KeyDown(KeyCode::Enter | KeyCode::NumpadEnter)) => {
-                self.console.enter(cvars);
+                self.console.enter();
                 self.update_ui_prompt(ui);
                 self.update_ui_history(ui);
             }
@@ -171,6 +185,24 @@ impl FyroxConsole {
         }
     }
 
+    /// Run commands entered by the user or from scripts.
+    ///
+    /// Call this every frame, even when the console is closed, so scripts using `wait` keep running.
+    ///
+    /// `context` is passed to custom commands, e.g. the game state, use `&mut ()` if they don't need it.
+    pub fn update(
+        &mut self,
+        ui: &mut UserInterface,
+        cvars: &mut impl SetGet,
+        context: &mut dyn Any,
+    ) {
+        let len = self.console.history.len();
+        self.console.run_buffer(cvars, context);
+        if self.console.history.len() != len {
+            self.update_ui_history(ui);
+        }
+    }
+
     fn update_ui_prompt(&mut self, ui: &mut UserInterface) {
         ui.send_message(TextMessage::text(
             self.prompt_text_box,
@@ -210,6 +242,34 @@ impl FyroxConsole {
         self.is_open
     }
 
+    /// Allow or disallow changing cvars marked with `#[cvars(flags(cheat))]`.
+    pub fn set_cheats(&mut self, cheats: bool) {
+        self.console.cheats = cheats;
+    }
+
+    /// Custom commands such as `map` or `kick`.
+    pub fn commands_mut(&mut self) -> &mut Commands {
+        &mut self.console.commands
+    }
+
+    /// Load input history from previous sessions from `path` and save it there after every command.
+    ///
+    /// Only the last `max_entries` lines are kept.
+    /// Use [`default_history_path`] to get a path in the user's state directory.
+    pub fn enable_persistent_history(
+        &mut self,
+        path: impl Into<PathBuf>,
+        max_entries: usize,
+    ) -> io::Result<()> {
+        self.console.enable_persistent_history(path, max_entries)
+    }
+
+    /// Run the script at `path` (e.g. `autoexec.cfg` in the game's config directory)
+    /// during the next `update` if it exists.
+    pub fn autoexec(&mut self, path: impl AsRef<Path>) {
+        self.console.autoexec(path);
+    }
+
     /// Open the console.
     ///
     /// If your game grabs the mouse, you can save the previous state here
@@ -1,6 +1,12 @@
 #![doc = include_str!("../README.md")]
 #![warn(missing_docs)]
 
+use std::{
+    any::Any,
+    io,
+    path::{Path, PathBuf},
+};
+
 use macroquad::{
     prelude::*,
     ui::{
@@ -13,6 +19,8 @@ use macroquad::{
 use cvars::SetGet;
 use cvars_console::Console;
 
+pub use cvars_console::{default_history_path, Arg, Command, CommandHandler, Commands};
+
 /// In-game console for the Macroquad game engine.
 ///
 /// It handles all the input and drawing, you just need to call `update` every frame after rendering.
@@ -37,22 +45,36 @@ impl MacroquadConsole {
         }
     }
 
-    /// Process input, handle opening and closing, draw the console.
+    /// Process input, handle opening and closing, draw the console, run entered commands.
     ///
     /// Call this every frame after your game's rendering code so the console is drawn on top.
-    pub fn update(&mut self, cvars: &mut dyn SetGet) {
+    /// Call it even when the console is closed so scripts using `wait` keep running.
+    ///
+    /// `context` is passed to custom commands, e.g. the game state, use `&mut ()` if they don't need it.
+    pub fn update(&mut self, cvars: &mut dyn SetGet, context: &mut dyn Any) {
         self.input_prev = self.input;
         self.input = get_input();
 
+        let was_open = self.is_open;
+        let prompt_before = self.console.prompt.clone();
         self.open_close();
 
         if self.is_open {
-            self.process_input();
+            self.process_input(cvars);
             self.draw_console();
+            if !was_open {
+                // The key which opened the console (e.g. the semicolon) gets typed into the prompt
+                // when opening it (but interestingly not closing).
+                // Throw away anything typed on the frame the console opened.
+                // LATER A less hacky input system would be great.
+                self.console.prompt = prompt_before;
+            }
             if !self.input_prev.enter && self.input.enter && !self.console.prompt.is_empty() {
-                self.console.enter(cvars);
+                self.console.enter();
             }
         }
+
+        self.console.run_buffer(cvars, context);
     }
 
     /// Open or close the console based on user's input.
@@ -69,15 +91,9 @@ impl MacroquadConsole {
     }
 
     /// Sanitize input text, handle cycling through history, etc.
-    fn process_input(&mut self) {
-        // The semicolon (default console bind) gets typed into the console
-        // when opening it (but interestingly not closing).
-        // Just disallow it completely we don't allow multiple commands on one line
-        // so there's currently no need for it.
-        // This has the side effect, that the text cursor moves one char to the right
-        // with each open/close cycle but that's OK.
-        // LATER A less hacky input system would be great.
-        self.console.prompt = self.console.prompt.replace(';', "");
+    fn process_input(&mut self, cvars: &dyn SetGet) {
+        // Tab is used for completion, it should never end up in the prompt.
+        self.console.prompt = self.console.prompt.replace('\t', "");
 
         // Detect key pressed based on previous and current state.
         // MQ's UI doesn't seem to have a built-in way to detecting keyboard events.
@@ -85,6 +101,7 @@ impl MacroquadConsole {
         let pressed_down = !self.input_prev.down && self.input.down;
         let pressed_page_up = !self.input_prev.page_up && self.input.page_up;
         let pressed_page_down = !self.input_prev.page_down && self.input.page_down;
+        let pressed_tab = !self.input_prev.tab && self.input.tab;
 
         if pressed_up {
             self.console.history_back();
@@ -95,6 +112,11 @@ impl MacroquadConsole {
             self.console.history_forward();
         }
 
+        // MQ's UI doesn't let us read or set the text cursor so always complete at the end.
+        if pressed_tab {
+            self.console.complete_prompt(cvars);
+        }
+
         // Scroll history up
         let count = 10; // LATER configurable
         if pressed_page_up {
@@ -196,6 +218,34 @@ impl MacroquadConsole {
     pub fn is_open(&self) -> bool {
         self.is_open
     }
+
+    /// Allow or disallow changing cvars marked with `#[cvars(flags(cheat))]`.
+    pub fn set_cheats(&mut self, cheats: bool) {
+        self.console.cheats = cheats;
+    }
+
+    /// Custom commands such as `map` or `kick`.
+    pub fn commands_mut(&mut self) -> &mut Commands {
+        &mut self.console.commands
+    }
+
+    /// Load input history from previous sessions from `path` and save it there after every command.
+    ///
+    /// Only the last `max_entries` lines are kept.
+    /// Use [`default_history_path`] to get a path in the user's state directory.
+    pub fn enable_persistent_history(
+        &mut self,
+        path: impl Into<PathBuf>,
+        max_entries: usize,
+    ) -> io::Result<()> {
+        self.console.enable_persistent_history(path, max_entries)
+    }
+
+    /// Run the script at `path` (e.g. `autoexec.cfg` in the game's config directory)
+    /// during the next `update` if it exists.
+    pub fn autoexec(&mut self, path: impl AsRef<Path>) {
+        self.console.autoexec(path);
+    }
 }
 
 /// Configuration for the console.
@@ -239,6 +289,7 @@ struct ConsoleInput {
     down: bool,
     page_up: bool,
     page_down: bool,
+    tab: bool,
 }
 
 impl ConsoleInput {
@@ -270,6 +321,9 @@ fn get_input() -> ConsoleInput {
     if are_keys_pressed(&[KeyCode::PageDown]) {
         input.page_down = true;
     }
+    if are_keys_pressed(&[KeyCode::Tab]) {
+        input.tab = true;
+    }
     input
 }
 
@@ -0,0 +1,337 @@
+use std::{any::Any, fmt, sync::Arc};
+
+use cvars::SetGet;
+
+use crate::Console;
+
+/// A callback which runs a custom console command.
+///
+/// The arguments are the console (e.g. to print output), the cvars,
+/// the game context passed to [`Console::run_buffer`] and the command's arguments.
+/// The number of arguments is checked against the command's [`Arg`]s before it's called.
+/// Returning `Err` prints the message in the console.
+pub type CommandHandler = Arc<
+    dyn Fn(&mut Console, &mut dyn SetGet, &mut dyn Any, &[&str]) -> Result<(), String>
+        + Send
+        + Sync,
+>;
+
+/// A custom console command such as `map` or `kick`.
+///
+/// Register it using [`Commands::add`].
+#[derive(Clone)]
+pub struct Command {
+    /// What the user types to run the command.
+    pub name: String,
+    /// A short description shown by `help`.
+    pub help: String,
+    /// The arguments the command takes, shown by `help` and checked before running the command.
+    pub args: Vec<Arg>,
+    /// The function which runs the command.
+    pub handler: CommandHandler,
+}
+
+impl Command {
+    /// Create a command which takes no arguments, add them using [`arg`](Command::arg)
+    /// and [`optional_arg`](Command::optional_arg).
+    pub fn new<F>(name: impl Into<String>, help: impl Into<String>, handler: F) -> Self
+    where
+        F: Fn(&mut Console, &mut dyn SetGet, &mut dyn Any, &[&str]) -> Result<(), String>
+            + Send
+            + Sync
+            + 'static,
+    {
+        Self {
+            name: name.into(),
+            help: help.into(),
+            args: Vec::new(),
+            handler: Arc::new(handler),
+        }
+    }
+
+    /// Add a required argument.
+    ///
+    /// The name is only used in `help` and error messages.
+    pub fn arg(mut self, name: impl Into<String>) -> Self {
+        self.args.push(Arg {
+            name: name.into(),
+            optional: false,
+        });
+        self
+    }
+
+    /// Add an optional argument.
+    ///
+    /// Optional arguments should come after all the required ones.
+    pub fn optional_arg(mut self, name: impl Into<String>) -> Self {
+        self.args.push(Arg {
+            name: name.into(),
+            optional: true,
+        });
+        self
+    }
+
+    /// How to use the command, e.g. `kick <player> [reason]`.
+    pub fn usage(&self) -> String {
+        let mut usage = self.name.clone();
+        for arg in &self.args {
+            if arg.optional {
+                usage.push_str(&format!(" [{}]", arg.name));
+            } else {
+                usage.push_str(&format!(" <{}>", arg.name));
+            }
+        }
+        usage
+    }
+
+    /// Whether the command can be called with `count` arguments.
+    pub fn accepts(&self, count: usize) -> bool {
+        let required = self.args.iter().filter(|arg| !arg.optional).count();
+        required <= count && count <= self.args.len()
+    }
+}
+
+impl fmt::Debug for Command {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Closures don't impl Debug.
+        f.debug_struct("Command")
+            .field("name", &self.name)
+            .field("help", &self.help)
+            .field("args", &self.args)
+            .finish_non_exhaustive()
+    }
+}
+
+/// An argument of a custom console command.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Arg {
+    /// The argument's name, only used in `help` and error messages.
+    pub name: String,
+    /// Whether the argument can be left out.
+    pub optional: bool,
+}
+
+/// Custom console commands.
+///
+/// The console first checks builtin commands such as `help`,
+/// then custom commands and finally cvars, so a custom command with the same name as a cvar
+/// shadows the cvar in the console. Custom commands can't have the same name as builtin commands.
+///
+/// To change other parts of the game, handlers get a context - whatever the game passes
+/// to [`Console::run_buffer`] (or the frontend's `update` method), e.g. the game state.
+/// Handlers downcast it to the type they expect.
+///
+/// # Example
+///
+/// ```rust
+/// use cvars::cvars;
+/// use cvars_console::{Command, Console};
+///
+/// cvars! {
+///     sv_maxplayers: i32 = 8,
+/// }
+///
+/// struct Game {
+///     players: Vec<String>,
+/// }
+///
+/// let mut cvars = Cvars::default();
+/// let mut game = Game {
+///     players: vec!["Alice".to_owned(), "Bob".to_owned()],
+/// };
+/// let mut console = Console::new();
+///
+/// let kick = Command::new("kick", "Kick a player", |console, _cvars, context, args| {
+///     let game = context.downcast_mut::<Game>().ok_or("kick needs a game")?;
+///     game.players.retain(|player| player != args[0]);
+///     console.print(format!("Kicked {}", args[0]));
+///     Ok(())
+/// })
+/// .arg("player")
+/// .optional_arg("reason");
+/// console.commands.add(kick);
+///
+/// console.prompt = "kick Bob".to_owned();
+/// console.enter();
+/// console.run_buffer(&mut cvars, &mut game);
+/// assert_eq!(game.players, ["Alice"]);
+/// assert_eq!(console.history.last().unwrap().text, "Kicked Bob");
+///
+/// console.prompt = "kick".to_owned();
+/// console.enter();
+/// console.run_buffer(&mut cvars, &mut game);
+/// assert_eq!(console.history.last().unwrap().text, "usage: kick <player> [reason]");
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Commands {
+    commands: Vec<Command>,
+}
+
+impl Commands {
+    /// Creates an empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers a command, replacing any previously registered command with the same name.
+    ///
+    /// # Panics
+    ///
+    /// If the name is the same as a builtin command such as `help` or `exec`
+    /// because the builtin command would always run instead.
+    pub fn add(&mut self, command: Command) {
+        assert!(
+            !is_builtin(&command.name),
+            "custom command {} has the same name as a builtin command",
+            command.name
+        );
+        self.remove(&command.name);
+        self.commands.push(command);
+    }
+
+    /// Removes the command named `name` if it exists.
+    pub fn remove(&mut self, name: &str) {
+        self.commands.retain(|command| command.name != name);
+    }
+
+    /// Returns the command named `name` if it exists.
+    pub fn get(&self, name: &str) -> Option<&Command> {
+        self.commands.iter().find(|command| command.name == name)
+    }
+
+    /// Returns all registered commands in the order they were added.
+    pub fn iter(&self) -> impl Iterator<Item = &Command> {
+        self.commands.iter()
+    }
+
+    /// Returns `true` if no commands are registered.
+    pub fn is_empty(&self) -> bool {
+        self.commands.is_empty()
+    }
+}
+
+fn is_builtin(name: &str) -> bool {
+    // `?` is an alias for `help` which isn't offered by completion.
+    name == "?" || crate::COMMANDS.contains(&name)
+}
+
+#[cfg(test)]
+mod tests {
+    use cvars::cvars;
+
+    use super::*;
+
+    cvars! {
+        g_lives: i32 = 3,
+    }
+
+    struct Game {
+        map: String,
+    }
+
+    fn console_with_commands() -> Console {
+        let mut console = Console::new();
+        let map = Command::new(
+            "map",
+            "Change the map",
+            |_console, _cvars, context, args| {
+                let game = context.downcast_mut::<Game>().ok_or("map needs a game")?;
+                game.map = args[0].to_owned();
+                Ok(())
+            },
+        )
+        .arg("name");
+        console.commands.add(map);
+        let kill = Command::new("kill", "Lose a life", |_console, cvars, _context, _args| {
+            let lives: i32 = cvars.get_string("g_lives").unwrap().parse().unwrap();
+            if lives == 0 {
+                return Err("no lives left".to_owned());
+            }
+            cvars
+                .set_str("g_lives", &(lives - 1).to_string())
+                .map_err(|err| err.to_string())
+        });
+        console.commands.add(kill);
+        console
+    }
+
+    fn run(console: &mut Console, cvars: &mut Cvars, game: &mut Game, input: &str) {
+        console.prompt = input.to_owned();
+        console.enter();
+        console.run_buffer(cvars, game);
+    }
+
+    fn last_line(console: &Console) -> &str {
+        &console.history.last().unwrap().text
+    }
+
+    #[test]
+    fn help_lists_custom_commands() {
+        let mut console = console_with_commands();
+        let mut game = Game { map: String::new() };
+        run(&mut console, &mut Cvars::default(), &mut game, "help");
+        let lines: Vec<_> = console.history.iter().map(|line| &line.text).collect();
+        let commands = lines.iter().position(|&line| line == "Commands:").unwrap();
+        assert_eq!(
+            lines[commands + 1..],
+            [
+                &format!("    {:<20} Change the map", "map <name>"),
+                &format!("    {:<20} Lose a life", "kill"),
+            ]
+        );
+    }
+
+    #[test]
+    fn complete_custom_commands() {
+        let mut console = console_with_commands();
+        console.prompt = "k".to_owned();
+        let completion = console.complete(&Cvars::default(), 1);
+        assert_eq!(completion.candidates, ["kill"]);
+        assert_eq!(console.prompt, "kill");
+
+        console.prompt = "m".to_owned();
+        let completion = console.complete(&Cvars::default(), 1);
+        assert_eq!(completion.candidates, ["map"]);
+    }
+
+    #[test]
+    fn run_custom_commands() {
+        let mut console = console_with_commands();
+        let mut cvars = Cvars::default();
+        let mut game = Game { map: String::new() };
+
+        run(&mut console, &mut cvars, &mut game, "map dm1; kill");
+        assert_eq!(game.map, "dm1");
+        assert_eq!(cvars.g_lives, 2);
+
+        // Wrong number of arguments
+        run(&mut console, &mut cvars, &mut game, "map");
+        assert_eq!(last_line(&console), "usage: map <name>");
+        run(&mut console, &mut cvars, &mut game, "map dm2 dm3");
+        assert_eq!(last_line(&console), "usage: map <name>");
+        assert_eq!(game.map, "dm1");
+
+        // Errors returned by the handler
+        cvars.g_lives = 0;
+        run(&mut console, &mut cvars, &mut game, "kill");
+        assert_eq!(last_line(&console), "no lives left");
+        console.prompt = "map dm2".to_owned();
+        console.enter();
+        console.run_buffer(&mut cvars, &mut ());
+        assert_eq!(last_line(&console), "map needs a game");
+    }
+
+    #[test]
+    #[should_panic(expected = "custom command exec has the same name as a builtin command")]
+    fn builtin_name() {
+        let exec = Command::new("exec", "", |_, _, _, _| Ok(()));
+        Commands::new().add(exec);
+    }
+
+    #[test]
+    #[should_panic(expected = "custom command ? has the same name as a builtin command")]
+    fn builtin_alias() {
+        let help = Command::new("?", "", |_, _, _, _| Ok(()));
+        Commands::new().add(help);
+    }
+}
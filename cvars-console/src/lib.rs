@@ -1,9 +1,22 @@
 #![doc = include_str!("../README.md")]
 #![warn(missing_docs)]
 
-use std::mem;
+mod commands;
 
-use cvars::SetGet;
+use std::{
+    any::Any,
+    collections::VecDeque,
+    env,
+    ffi::OsString,
+    fs, io, mem,
+    path::{Path, PathBuf},
+    sync::Arc,
+};
+
+use cvars::{config, CvarError, CvarFlags, SetGet};
+
+pub use commands::{Arg, Command, CommandHandler, Commands};
+pub use cvars::config::{tokenize, LexError};
 
 /// Engine-independant parts of the in-game console.
 #[derive(Debug, Clone, Default)]
@@ -24,13 +37,42 @@ pub struct Console {
     /// You should prepend input lines with "> " or something similar when displaying them.
     pub history: Vec<HistoryLine>,
 
+    /// Input lines from previous sessions, older than anything in `history`.
+    ///
+    /// They're only used when walking through history using up and down keys, not displayed.
+    loaded_history: Vec<String>,
+
+    /// Where to save input history, None if it's not saved.
+    history_file: Option<HistoryFile>,
+
     /// Where we are in the history view when scrolling using page up and down keys.
     ///
     /// This index is *one past* the last line to be displayed at the *bottom*
     /// so that you can use it as the high end of a range.
     pub history_view_end: usize,
+
+    /// Whether cvars marked with `#[cvars(flags(cheat))]` can be changed.
+    ///
+    /// Cvars marked with `#[cvars(flags(read_only))]` can never be changed from the console.
+    pub cheats: bool,
+
+    /// Custom commands such as `map` or `kick`, see [`Commands`].
+    pub commands: Commands,
+
+    /// Commands waiting to run, see [`run_buffer`](Console::run_buffer).
+    buffer: VecDeque<BufferedCommand>,
+
+    /// How many more calls to `run_buffer` have to happen before the buffer continues after `wait`.
+    wait_frames: usize,
+
+    /// The last tab completion so that repeated calls cycle through its candidates.
+    /// None if the prompt changed since then.
+    completion: Option<CompletionState>,
 }
 
+/// Built-in console commands, used for completion.
+const COMMANDS: &[&str] = &["changed", "exec", "help", "reset", "reset_all", "wait"];
+
 impl Console {
     /// Create a new console backend.
     pub fn new() -> Self {
@@ -39,7 +81,14 @@ impl Console {
             prompt_saved: None,
             prompt_history_index: None,
             history: Vec::new(),
+            loaded_history: Vec::new(),
+            history_file: None,
             history_view_end: 0,
+            cheats: false,
+            commands: Commands::new(),
+            buffer: VecDeque::new(),
+            wait_frames: 0,
+            completion: None,
         }
     }
 
@@ -47,21 +96,29 @@ impl Console {
     ///
     /// Save the prompt so that users can go back in history,
     /// then come back to present and get what they typed back.
+    ///
+    /// After this session's input, continue with input loaded by [`enable_persistent_history`](Console::enable_persistent_history).
     pub fn history_back(&mut self) {
-        let search_slice = if let Some(hi) = self.prompt_history_index {
-            &self.history[0..hi]
-        } else {
-            &self.history[..]
-        };
-        if let Some(new_index) = search_slice
+        // Indices below `loaded` point into `loaded_history`, the rest into `history`.
+        let loaded = self.loaded_history.len();
+        let end = self
+            .prompt_history_index
+            .unwrap_or(loaded + self.history.len());
+        let search_slice = &self.history[0..end.saturating_sub(loaded)];
+        let new_index = match search_slice
             .iter()
             .rposition(|hist_line| hist_line.is_input)
         {
+            Some(index) => Some(loaded + index),
+            // All loaded lines are input.
+            None => end.min(loaded).checked_sub(1),
+        };
+        if let Some(new_index) = new_index {
             self.prompt_history_index = Some(new_index);
             if self.prompt_saved.is_none() {
                 self.prompt_saved = Some(self.prompt.clone());
             }
-            self.prompt = self.history[new_index].text.clone();
+            self.prompt = self.history_input(new_index).to_owned();
         }
     }
 
@@ -70,18 +127,25 @@ impl Console {
     /// Restore the saved prompt if get to the end.
     pub fn history_forward(&mut self) {
         if let Some(index) = self.prompt_history_index {
+            let loaded = self.loaded_history.len();
             // Start after the current, otherwise we'd immediately find the current, not the next.
             // It's ok to index 1 past the end.
             let begin = index + 1;
-            let search_slice = &self.history[begin..];
-            if let Some(local_index) = search_slice.iter().position(|hist_line| hist_line.is_input)
-            {
+            let new_index = if begin < loaded {
+                Some(begin)
+            } else {
                 // `position` starts counting from the iterator's start,
                 // not from history's start so we add the found index to what we skipped
                 // instead of using it directly.
-                let new_index = begin + local_index;
+                let search_slice = &self.history[begin - loaded..];
+                search_slice
+                    .iter()
+                    .position(|hist_line| hist_line.is_input)
+                    .map(|local_index| begin + local_index)
+            };
+            if let Some(new_index) = new_index {
                 self.prompt_history_index = Some(new_index);
-                self.prompt = self.history[new_index].text.clone();
+                self.prompt = self.history_input(new_index).to_owned();
             } else {
                 // We're at the end of history, restore the saved prompt.
                 self.prompt_history_index = None;
@@ -90,6 +154,195 @@ impl Console {
         }
     }
 
+    /// The input line at `index` as used by `prompt_history_index`.
+    fn history_input(&self, index: usize) -> &str {
+        let loaded = self.loaded_history.len();
+        if index < loaded {
+            &self.loaded_history[index]
+        } else {
+            &self.history[index - loaded].text
+        }
+    }
+
+    /// Load input history from previous sessions from `path` and save it there after every command.
+    ///
+    /// Consecutive identical lines are saved only once
+    /// and only the last `max_entries` lines are kept.
+    /// A missing file is not an error, it's created when the first command is entered.
+    /// Errors when saving are printed in the console.
+    ///
+    /// Use [`default_history_path`] to get a path in the user's state directory.
+    pub fn enable_persistent_history(
+        &mut self,
+        path: impl Into<PathBuf>,
+        max_entries: usize,
+    ) -> io::Result<()> {
+        let path = path.into();
+        self.loaded_history = match fs::read_to_string(&path) {
+            Ok(text) => text
+                .lines()
+                .filter(|line| !line.is_empty())
+                .map(str::to_owned)
+                .collect(),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Vec::new(),
+            Err(err) => return Err(err),
+        };
+        // The index would point to different lines now.
+        self.prompt_history_index = None;
+        self.history_file = Some(HistoryFile { path, max_entries });
+        Ok(())
+    }
+
+    /// Write loaded and this session's input to the history file if it's enabled.
+    fn save_history(&self) -> io::Result<()> {
+        let Some(file) = &self.history_file else {
+            return Ok(());
+        };
+
+        let inputs = self.loaded_history.iter().map(String::as_str).chain(
+            self.history
+                .iter()
+                .filter(|hist_line| hist_line.is_input)
+                .map(|hist_line| hist_line.text.as_str()),
+        );
+        let mut lines: Vec<&str> = Vec::new();
+        for input in inputs {
+            // Each entry is one line in the file.
+            if input.trim().is_empty() || input.contains('\n') {
+                continue;
+            }
+            if lines.last() != Some(&input) {
+                lines.push(input);
+            }
+        }
+        let skip = lines.len().saturating_sub(file.max_entries);
+
+        let mut text = String::new();
+        for line in &lines[skip..] {
+            text.push_str(line);
+            text.push('\n');
+        }
+        if let Some(dir) = file.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(&file.path, text)
+    }
+
+    /// Complete the word under the cursor.
+    ///
+    /// `cursor` is a byte index into the prompt, use `prompt.len()` if the frontend doesn't track it.
+    ///
+    /// The first word is completed against cvar names and builtin commands,
+    /// the argument of `reset` against cvar names
+    /// and the value after a cvar name against its allowed values
+    /// (`true` and `false` for bools, the list from `#[cvars(one_of = [...])]` for other types, e.g. enums).
+    ///
+    /// The word is replaced by the first candidate,
+    /// calling this again without changing the prompt replaces it by the next one.
+    pub fn complete(&mut self, cvars: &dyn SetGet, cursor: usize) -> Completion {
+        let mut state = match self.completion.take() {
+            Some(mut state) if state.prompt == self.prompt && state.cursor == cursor => {
+                state.index = (state.index + 1) % state.candidates.len();
+                state
+            }
+            _ => match self.new_completion(cvars, cursor) {
+                Some(state) => state,
+                None => {
+                    return Completion {
+                        candidates: Vec::new(),
+                        index: 0,
+                        cursor,
+                    }
+                }
+            },
+        };
+
+        let candidate = &state.candidates[state.index];
+        self.prompt
+            .replace_range(state.word_start..state.word_end, candidate);
+        state.word_end = state.word_start + candidate.len();
+        state.cursor = state.word_end;
+        state.prompt = self.prompt.clone();
+
+        let completion = Completion {
+            candidates: state.candidates.clone(),
+            index: state.index,
+            cursor: state.cursor,
+        };
+        self.completion = Some(state);
+        completion
+    }
+
+    /// Complete the last word of the prompt, meant to be called when the user presses Tab.
+    ///
+    /// For frontends which don't know where the cursor is.
+    /// When there are multiple candidates, they're printed to history
+    /// whenever the first one is put in the prompt.
+    pub fn complete_prompt(&mut self, cvars: &dyn SetGet) -> Completion {
+        let completion = self.complete(cvars, self.prompt.len());
+        if completion.index == 0 && completion.candidates.len() > 1 {
+            self.print(completion.candidates.join("  "));
+        }
+        completion
+    }
+
+    /// Find the word under the cursor and what it can be completed to.
+    fn new_completion(&self, cvars: &dyn SetGet, cursor: usize) -> Option<CompletionState> {
+        let mut cursor = cursor.min(self.prompt.len());
+        while !self.prompt.is_char_boundary(cursor) {
+            cursor -= 1;
+        }
+
+        let before = &self.prompt[..cursor];
+        let word_start = before
+            .rfind(|c: char| c.is_whitespace() || c == ';')
+            .map_or(0, |i| i + before[i..].chars().next().unwrap().len_utf8());
+        let word_end = self.prompt[cursor..]
+            .find(|c: char| c.is_whitespace() || c == ';')
+            .map_or(self.prompt.len(), |i| cursor + i);
+        let prefix = &self.prompt[word_start..cursor];
+        // Only look at the current command if there are several separated by `;`.
+        let command_start = self.prompt[..word_start].rfind(';').map_or(0, |i| i + 1);
+        let words: Vec<_> = self.prompt[command_start..word_start]
+            .split_whitespace()
+            .collect();
+
+        let cvar_names = || cvars.cvar_infos().iter().map(|info| info.name.to_owned());
+        let mut candidates: Vec<String> = match words[..] {
+            [] => COMMANDS
+                .iter()
+                .map(|&command| command.to_owned())
+                .chain(self.commands.iter().map(|command| command.name.clone()))
+                .chain(cvar_names())
+                .collect(),
+            ["reset"] => cvar_names().collect(),
+            // Use the formatted values, `CvarInfo::one_of` contains the source code
+            // which for enums is not valid input.
+            // Check the actual type, `CvarInfo::ty` is the source code which can be e.g. an alias.
+            [cvar_name] => match cvars.get_any(cvar_name) {
+                Some(value) if value.is::<bool>() => vec!["true".to_owned(), "false".to_owned()],
+                Some(_) => cvars.allowed_values(cvar_name).unwrap_or_default(),
+                None => Vec::new(),
+            },
+            _ => Vec::new(),
+        };
+        candidates.retain(|candidate| candidate.starts_with(prefix));
+        candidates.sort_unstable();
+        candidates.dedup();
+        if candidates.is_empty() {
+            return None;
+        }
+
+        Some(CompletionState {
+            prompt: self.prompt.clone(),
+            cursor,
+            word_start,
+            word_end,
+            candidates,
+            index: 0,
+        })
+    }
+
     /// Scroll up in the history view.
     pub fn history_scroll_up(&mut self, count: usize) {
         self.history_view_end = self.history_view_end.saturating_sub(count);
@@ -105,26 +358,163 @@ impl Console {
         self.history_view_end = (self.history_view_end + count).min(self.history.len());
     }
 
-    /// The user pressed enter - process the line of text
-    pub fn enter(&mut self, cvars: &mut dyn SetGet) {
+    /// The user pressed enter - add the line of text to the command buffer.
+    ///
+    /// The commands run during the next call to [`run_buffer`](Console::run_buffer).
+    pub fn enter(&mut self) {
         let cmd = mem::take(&mut self.prompt);
 
         self.print_input(&cmd);
-
-        // The actual command parsing logic
-        let res = self.execute_command(cvars, &cmd);
-        if let Err(msg) = res {
-            self.print(msg);
+        if let Err(err) = self.save_history() {
+            self.print(format!("failed to save history: {err}"));
         }
+        self.queue(&cmd);
 
         // Entering a new command resets the user's position in history to the end.
         self.prompt_history_index = None;
     }
 
-    /// Parse what the user typed and get or set a cvar
-    fn execute_command(&mut self, cvars: &mut dyn SetGet, cmd: &str) -> Result<(), String> {
-        // Splitting on whitespace also in effect trims leading and trailing whitespace.
-        let mut parts = cmd.split_whitespace();
+    /// Split the text into commands and add them to the end of the command buffer.
+    ///
+    /// If the text fails to split (e.g. because of an unterminated quote), none of it is added
+    /// and the error is printed.
+    pub fn queue(&mut self, text: &str) {
+        match tokenize(text) {
+            Ok(commands) => {
+                let commands = commands.into_iter().map(|words| BufferedCommand {
+                    words,
+                    source: None,
+                });
+                self.buffer.extend(commands);
+            }
+            Err(err) => self.print(err.to_string()),
+        }
+    }
+
+    /// Queue the script at `path` (e.g. `autoexec.cfg` in the game's config directory)
+    /// so it runs during the next [`run_buffer`](Console::run_buffer).
+    ///
+    /// Does nothing if the file doesn't exist, the script is optional for the user to create.
+    pub fn autoexec(&mut self, path: impl AsRef<Path>) {
+        let path = path.as_ref();
+        if !path.exists() {
+            return;
+        }
+        // Commands are text so the path has to be too.
+        match path.to_str() {
+            Some(path) => self.buffer.push_back(BufferedCommand {
+                words: vec!["exec".to_owned(), path.to_owned()],
+                source: None,
+            }),
+            None => self.print(format!(
+                "autoexec path {} is not valid UTF-8",
+                path.display()
+            )),
+        }
+    }
+
+    /// Run commands from the command buffer until it's empty or until `wait`.
+    ///
+    /// Call this once every frame, even when the console is closed -
+    /// `wait <n>` makes the rest of the buffer run during the n-th following call.
+    ///
+    /// `context` is passed to custom [commands](Commands), it can be anything they need
+    /// to access, e.g. the game state. Use `&mut ()` if they don't need anything.
+    ///
+    /// An error in one command is printed and doesn't prevent running the following ones.
+    /// Errors in scripts are prefixed with the script's path and line number.
+    pub fn run_buffer(&mut self, cvars: &mut dyn SetGet, context: &mut dyn Any) {
+        if self.wait_frames > 0 {
+            self.wait_frames -= 1;
+            if self.wait_frames > 0 {
+                return;
+            }
+        }
+
+        while let Some(command) = self.buffer.pop_front() {
+            // The actual command parsing logic
+            let res = self.execute_command(cvars, context, &command);
+            if let Err(msg) = res {
+                match &command.source {
+                    Some(source) => self.print(format!(
+                        "{}:{}: {msg}",
+                        source.script.path.display(),
+                        source.line
+                    )),
+                    None => self.print(msg),
+                }
+            }
+            if self.wait_frames > 0 {
+                break;
+            }
+        }
+    }
+
+    /// Whether there are commands waiting to run.
+    pub fn is_buffer_empty(&self) -> bool {
+        self.buffer.is_empty()
+    }
+
+    /// Read the script and insert its commands at the front of the buffer
+    /// so they run before the rest of the buffer.
+    ///
+    /// `parent` is the script which executed this one, if any.
+    fn exec(&mut self, path: &str, parent: Option<&Arc<Script>>) -> Result<(), String> {
+        // Compare canonical paths so a script is detected
+        // no matter which relative path was used to refer to it.
+        let canonical = fs::canonicalize(path).unwrap_or_else(|_| PathBuf::from(path));
+        let mut chain = vec![path.to_owned()];
+        let mut ancestor = parent;
+        let mut recursive = false;
+        while let Some(script) = ancestor {
+            chain.push(script.path.display().to_string());
+            recursive |= script.canonical == canonical;
+            ancestor = script.parent.as_ref();
+        }
+        if recursive {
+            chain.reverse();
+            return Err(format!("recursive exec: {}", chain.join(" -> ")));
+        }
+
+        let text =
+            fs::read_to_string(path).map_err(|err| format!("failed to read {path}: {err}"))?;
+        let script = Arc::new(Script {
+            path: PathBuf::from(path),
+            canonical,
+            parent: parent.cloned(),
+        });
+
+        // Split line by line so errors can report line numbers.
+        let mut commands = Vec::new();
+        for (index, line) in text.lines().enumerate() {
+            let source = Source {
+                script: Arc::clone(&script),
+                line: index + 1,
+            };
+            match tokenize(line) {
+                Ok(line_commands) => {
+                    commands.extend(line_commands.into_iter().map(|words| BufferedCommand {
+                        words,
+                        source: Some(source.clone()),
+                    }));
+                }
+                Err(err) => self.print(format!("{path}:{}: {err}", source.line)),
+            }
+        }
+        for command in commands.into_iter().rev() {
+            self.buffer.push_front(command);
+        }
+        Ok(())
+    }
+
+    /// Get or set a cvar or run a command
+    fn execute_command(
+        &mut self,
+        cvars: &mut dyn SetGet,
+        context: &mut dyn Any,
+        cmd: &BufferedCommand,
+    ) -> Result<(), String> {
+        let mut parts = cmd.words.iter().map(String::as_str);
 
         let cvar_name = match parts.next() {
             Some(name) => name,
@@ -135,13 +525,113 @@ impl Console {
             self.print("    help                 Print this message");
             self.print("    <cvar name>          Print the cvar's value");
             self.print("    <cvar name> <value>  Set the cvar's value");
+            self.print("    reset <cvar name>    Reset the cvar to its default value");
+            self.print(
+                "    reset_all            Reset all cvars which can be changed to their default values",
+            );
+            self.print("    changed              Print cvars which differ from their defaults");
+            self.print("    exec <file>          Run commands from a file");
+            self.print(
+                "    wait [frames]        Run the following commands after a number of frames",
+            );
+            self.print("Separate multiple commands by ';', quote values containing spaces.");
+            if !self.commands.is_empty() {
+                let lines: Vec<_> = self
+                    .commands
+                    .iter()
+                    .map(|command| format!("    {:<20} {}", command.usage(), command.help))
+                    .collect();
+                self.print("Commands:");
+                for line in lines {
+                    self.print(line);
+                }
+            }
+            return Ok(());
+        }
+        if cvar_name == "changed" {
+            if let Some(rest) = parts.next() {
+                return Err(format!("changed takes no arguments, found {rest}"));
+            }
+            let changed = cvars.changed();
+            if changed.is_empty() {
+                self.print("All cvars have their default values");
+            }
+            // Quoted so the lines can be copied into a script.
+            for cvar in changed {
+                self.print(format!(
+                    "{} {} (default: {})",
+                    cvar.name,
+                    config::quote(&cvar.value),
+                    config::quote(&cvar.default)
+                ));
+            }
+            return Ok(());
+        }
+        if cvar_name == "reset_all" {
+            if let Some(rest) = parts.next() {
+                return Err(format!("reset_all takes no arguments, found {rest}"));
+            }
+            // Not using `SetGet::reset_all` so the user can't change protected cvars this way.
+            let cheats = self.cheats;
+            let allowed = |flags: CvarFlags| !flags.read_only && (cheats || !flags.cheat);
+            cvars.reset_where(&allowed);
+            let skipped = cvars
+                .cvar_infos()
+                .iter()
+                .filter(|info| !allowed(info.flags))
+                .count();
+            if skipped > 0 {
+                self.print(format!(
+                    "Skipped {skipped} read-only or cheat protected cvars"
+                ));
+            }
+            return Ok(());
+        }
+        if cvar_name == "reset" {
+            let (Some(cvar_name), None) = (parts.next(), parts.next()) else {
+                return Err("usage: reset <cvar name>".to_owned());
+            };
+            self.check_flags(cvars, cvar_name)?;
+            return cvars
+                .reset(cvar_name)
+                .map_err(|err| error_message(cvars, err));
+        }
+
+        if cvar_name == "exec" {
+            let (Some(path), None) = (parts.next(), parts.next()) else {
+                return Err("usage: exec <file>".to_owned());
+            };
+            let parent = cmd.source.as_ref().map(|source| &source.script);
+            return self.exec(path, parent);
+        }
+        if cvar_name == "wait" {
+            let frames = match (parts.next(), parts.next()) {
+                (None, _) => 1,
+                (Some(frames), None) => frames
+                    .parse()
+                    .map_err(|_| format!("expected number of frames, found {frames}"))?,
+                (Some(_), Some(_)) => return Err("usage: wait [frames]".to_owned()),
+            };
+            self.wait_frames = frames;
             return Ok(());
         }
 
+        if let Some(command) = self.commands.get(cvar_name) {
+            let args: Vec<_> = parts.collect();
+            if !command.accepts(args.len()) {
+                return Err(format!("usage: {}", command.usage()));
+            }
+            // Clone the handler so it can borrow the console mutably.
+            let handler = Arc::clone(&command.handler);
+            return handler(self, cvars, context, &args);
+        }
+
         let cvar_value = match parts.next() {
             Some(val) => val,
             None => {
-                let val = cvars.get_string(cvar_name)?;
+                let val = cvars
+                    .get_string(cvar_name)
+                    .map_err(|err| error_message(cvars, err))?;
                 self.print(val);
                 return Ok(());
             }
@@ -149,7 +639,26 @@ impl Console {
         if let Some(rest) = parts.next() {
             return Err(format!("expected only cvar name and value, found {rest}"));
         }
-        cvars.set_str(cvar_name, cvar_value)
+        self.check_flags(cvars, cvar_name)?;
+        cvars
+            .set_str(cvar_name, cvar_value)
+            .map_err(|err| error_message(cvars, err))
+    }
+
+    /// Refuse to change read-only cvars and cheat cvars unless cheats are enabled.
+    fn check_flags(&self, cvars: &dyn SetGet, cvar_name: &str) -> Result<(), String> {
+        // Unknown cvars are reported by the operation itself.
+        if let Some(flags) = cvars.cvar_flags(cvar_name) {
+            if flags.read_only {
+                return Err(format!("{cvar_name} is read-only"));
+            }
+            if flags.cheat && !self.cheats {
+                return Err(format!(
+                    "{cvar_name} is cheat protected, enable cheats to change it"
+                ));
+            }
+        }
+        Ok(())
     }
 
     /// Print a line in the console and save it to history as output.
@@ -171,6 +680,104 @@ impl Console {
     }
 }
 
+/// Describe the error, suggest similar cvar names if the cvar doesn't exist.
+fn error_message(cvars: &dyn SetGet, err: CvarError) -> String {
+    let mut msg = err.to_string();
+    if let CvarError::NotFound { name } = &err {
+        let suggestions = cvars.suggest(name);
+        if !suggestions.is_empty() {
+            msg.push_str(", did you mean: ");
+            msg.push_str(&suggestions.join(", "));
+        }
+    }
+    msg
+}
+
+/// The result of [`Console::complete`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Completion {
+    /// All words the one under the cursor can be completed to, sorted.
+    ///
+    /// Empty if there's nothing to complete.
+    /// Frontends can show them when there's more than one, e.g. when `index` is 0.
+    pub candidates: Vec<String>,
+
+    /// Which candidate is now in the prompt.
+    pub index: usize,
+
+    /// Where the cursor should be now - the end of the completed word.
+    pub cursor: usize,
+}
+
+#[derive(Debug, Clone)]
+struct CompletionState {
+    /// The prompt after completing, if it's changed, the next completion starts from scratch.
+    prompt: String,
+    cursor: usize,
+    /// The range in the prompt replaced by the candidate.
+    word_start: usize,
+    word_end: usize,
+    candidates: Vec<String>,
+    index: usize,
+}
+
+/// Where the user's state directory says to save input history for `app_name`.
+///
+/// This is `$XDG_STATE_HOME/<app_name>/console_history`,
+/// falling back to `$HOME/.local/state/<app_name>/console_history`.
+/// Returns `None` if neither variable is set, e.g. on Windows - supply your own path there.
+pub fn default_history_path(app_name: &str) -> Option<PathBuf> {
+    history_path(env::var_os("XDG_STATE_HOME"), env::var_os("HOME"), app_name)
+}
+
+/// [`default_history_path`] with the environment variables passed in so it can be tested.
+fn history_path(
+    xdg_state_home: Option<OsString>,
+    home: Option<OsString>,
+    app_name: &str,
+) -> Option<PathBuf> {
+    let state_dir = xdg_state_home
+        .map(PathBuf::from)
+        // Relative paths are invalid according to the spec and should be ignored.
+        .filter(|dir| dir.is_absolute())
+        .or_else(|| home.map(|home| Path::new(&home).join(".local/state")))?;
+    Some(state_dir.join(app_name).join("console_history"))
+}
+
+/// Settings from [`Console::enable_persistent_history`].
+#[derive(Debug, Clone)]
+struct HistoryFile {
+    path: PathBuf,
+    max_entries: usize,
+}
+
+/// A command in the command buffer.
+#[derive(Debug, Clone)]
+struct BufferedCommand {
+    words: Vec<String>,
+    /// Where the command came from, None if the user typed it.
+    source: Option<Source>,
+}
+
+/// The location of a command in a script.
+#[derive(Debug, Clone)]
+struct Source {
+    script: Arc<Script>,
+    /// Line number, counted from 1.
+    line: usize,
+}
+
+/// A script run using `exec`.
+#[derive(Debug)]
+struct Script {
+    /// The path as the user wrote it, for error messages.
+    path: PathBuf,
+    /// The path used to detect recursion.
+    canonical: PathBuf,
+    /// The script which executed this one, None if it was executed by the user.
+    parent: Option<Arc<Script>>,
+}
+
 /// A line in the console's history view.
 ///
 /// Might have come from the user or is the result of running a command.
@@ -188,3 +795,501 @@ impl HistoryLine {
         Self { text, is_input }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::{fmt, process};
+
+    use cvars::cvars;
+
+    use super::*;
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    enum Split {
+        Vertical,
+        Horizontal,
+    }
+
+    impl std::str::FromStr for Split {
+        type Err = String;
+
+        fn from_str(s: &str) -> Result<Self, Self::Err> {
+            match s {
+                "vertical" => Ok(Split::Vertical),
+                "horizontal" => Ok(Split::Horizontal),
+                _ => Err(format!("unknown split {s}")),
+            }
+        }
+    }
+
+    impl fmt::Display for Split {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            match self {
+                Split::Vertical => write!(f, "vertical"),
+                Split::Horizontal => write!(f, "horizontal"),
+            }
+        }
+    }
+
+    cvars! {
+        g_armor: i32 = 100,
+        g_lives: i32 = 3,
+        g_loadout: String = "rifle".to_owned(),
+        // Completion must recognize bools by type, not by how it's written.
+        cl_fullscreen: std::primitive::bool = false,
+        #[cvars(one_of = [Split::Vertical, Split::Horizontal])]
+        cl_split: Split = Split::Vertical,
+        #[cvars(flags(cheat))]
+        sv_gravity: f64 = 800.0,
+        #[cvars(flags(read_only))]
+        sv_version: i32 = 1,
+    }
+
+    fn run(console: &mut Console, cvars: &mut Cvars, input: &str) {
+        console.prompt = input.to_owned();
+        console.enter();
+        console.run_buffer(cvars, &mut ());
+    }
+
+    fn last_line(console: &Console) -> &str {
+        &console.history.last().unwrap().text
+    }
+
+    /// A path in the temp dir which is unique to this test process and `name`.
+    fn temp_path(name: &str) -> PathBuf {
+        env::temp_dir().join(format!("cvars-console-test-{}-{name}", process::id()))
+    }
+
+    /// Write a script to a unique temp file and return its path.
+    fn write_script(name: &str, text: &str) -> String {
+        let path = temp_path(name);
+        fs::write(&path, text).unwrap();
+        path.to_str().unwrap().to_owned()
+    }
+
+    fn output(console: &Console) -> Vec<&str> {
+        console
+            .history
+            .iter()
+            .filter(|line| !line.is_input)
+            .map(|line| line.text.as_str())
+            .collect()
+    }
+
+    #[test]
+    fn exec_recursion() {
+        let mut console = Console::new();
+        let mut cvars = Cvars::default();
+        let a = temp_path("a.cfg");
+        let b = temp_path("b.cfg");
+        let a = a.to_str().unwrap();
+        let b = b.to_str().unwrap();
+        write_script("a.cfg", &format!("g_lives 1\nexec {b}"));
+        write_script("b.cfg", &format!("g_armor 1\nexec {a}\ng_loadout b"));
+
+        run(&mut console, &mut cvars, &format!("exec {a}"));
+        fs::remove_file(a).unwrap();
+        fs::remove_file(b).unwrap();
+        assert_eq!(
+            output(&console),
+            [format!("{b}:2: recursive exec: {a} -> {b} -> {a}")]
+        );
+        // Everything else still runs.
+        assert_eq!(cvars.g_lives, 1);
+        assert_eq!(cvars.g_armor, 1);
+        assert_eq!(cvars.g_loadout, "b");
+    }
+
+    #[test]
+    fn exec_errors_have_file_and_line() {
+        let mut console = Console::new();
+        let mut cvars = Cvars::default();
+        let path = write_script(
+            "errors.cfg",
+            "g_lives 1\n\ng_bla 2\ng_armor \"x\nwait x\ng_armor 2",
+        );
+
+        run(&mut console, &mut cvars, &format!("exec {path}"));
+        fs::remove_file(&path).unwrap();
+        let output = output(&console);
+        assert_eq!(output.len(), 3);
+        assert!(output[0].starts_with(&format!("{path}:4: column 9: unterminated double quote")));
+        assert!(output[1].starts_with(&format!("{path}:3: ")));
+        assert_eq!(
+            output[2],
+            format!("{path}:5: expected number of frames, found x")
+        );
+        assert_eq!(cvars.g_lives, 1);
+        assert_eq!(cvars.g_armor, 2);
+
+        run(&mut console, &mut cvars, &format!("exec {path}"));
+        assert!(last_line(&console).starts_with(&format!("failed to read {path}: ")));
+    }
+
+    #[test]
+    fn wait_defers_by_frames() {
+        let mut console = Console::new();
+        let mut cvars = Cvars::default();
+
+        run(
+            &mut console,
+            &mut cvars,
+            "g_lives 1; wait 3; g_lives 2; wait; g_lives 3",
+        );
+        assert_eq!(cvars.g_lives, 1);
+        console.run_buffer(&mut cvars, &mut ());
+        assert_eq!(cvars.g_lives, 1);
+        console.run_buffer(&mut cvars, &mut ());
+        assert_eq!(cvars.g_lives, 1);
+        console.run_buffer(&mut cvars, &mut ());
+        assert_eq!(cvars.g_lives, 2);
+        assert!(!console.is_buffer_empty());
+        console.run_buffer(&mut cvars, &mut ());
+        assert_eq!(cvars.g_lives, 3);
+        assert!(console.is_buffer_empty());
+
+        // Commands entered while waiting run after the ones already in the buffer.
+        run(&mut console, &mut cvars, "wait; g_lives 4");
+        console.prompt = "g_lives 5".to_owned();
+        console.enter();
+        console.run_buffer(&mut cvars, &mut ());
+        assert_eq!(cvars.g_lives, 5);
+    }
+
+    #[test]
+    fn exec_runs_before_rest_of_buffer() {
+        let mut console = Console::new();
+        let mut cvars = Cvars::default();
+        let path = write_script("order.cfg", "g_lives 1\nwait\ng_armor 1; g_lives 2");
+
+        run(&mut console, &mut cvars, &format!("exec {path}; g_lives 3"));
+        fs::remove_file(&path).unwrap();
+        assert_eq!(cvars.g_lives, 1);
+        assert_eq!(cvars.g_armor, 100);
+        console.run_buffer(&mut cvars, &mut ());
+        assert_eq!(cvars.g_armor, 1);
+        assert_eq!(cvars.g_lives, 3);
+        assert!(console.history.iter().all(|line| line.is_input));
+    }
+
+    #[test]
+    fn missing_autoexec() {
+        let path = temp_path("missing_autoexec.cfg");
+        assert!(!path.exists());
+        let mut console = Console::new();
+        console.autoexec(&path);
+        assert!(console.is_buffer_empty());
+        console.run_buffer(&mut Cvars::default(), &mut ());
+        assert!(console.history.is_empty());
+    }
+
+    #[test]
+    fn autoexec_runs_on_next_frame() {
+        let path = write_script("autoexec.cfg", "g_lives 5");
+        let mut console = Console::new();
+        let mut cvars = Cvars::default();
+        console.autoexec(&path);
+        assert!(!console.is_buffer_empty());
+        console.run_buffer(&mut cvars, &mut ());
+        fs::remove_file(&path).unwrap();
+        assert_eq!(cvars.g_lives, 5);
+    }
+
+    #[test]
+    fn history_walks_session_then_loaded_input() {
+        let path = write_script("history_walk", "l1\nl2\n");
+        let mut console = Console::new();
+        let mut cvars = Cvars::default();
+        console.enable_persistent_history(&path, 100).unwrap();
+        // Both commands print output which is skipped when walking history.
+        run(&mut console, &mut cvars, "s1");
+        run(&mut console, &mut cvars, "g_armor");
+        fs::remove_file(&path).unwrap();
+        assert_eq!(console.history.len(), 4);
+
+        console.prompt = "typing".to_owned();
+        let mut prompts = Vec::new();
+        for _ in 0..5 {
+            console.history_back();
+            prompts.push(console.prompt.clone());
+        }
+        assert_eq!(prompts, ["g_armor", "s1", "l2", "l1", "l1"]);
+
+        let mut prompts = Vec::new();
+        for _ in 0..5 {
+            console.history_forward();
+            prompts.push(console.prompt.clone());
+        }
+        assert_eq!(prompts, ["l2", "s1", "g_armor", "typing", "typing"]);
+        assert_eq!(console.prompt_history_index, None);
+
+        // Entering a command resets the position.
+        console.history_back();
+        console.history_back();
+        run(&mut console, &mut cvars, "g_lives");
+        console.history_back();
+        assert_eq!(console.prompt, "g_lives");
+    }
+
+    #[test]
+    fn history_without_loaded_input() {
+        let mut console = Console::new();
+        console.history_back();
+        assert_eq!(console.prompt, "");
+        assert_eq!(console.prompt_history_index, None);
+
+        console.print("only output");
+        console.prompt = "typing".to_owned();
+        console.history_back();
+        assert_eq!(console.prompt, "typing");
+        console.history_forward();
+        assert_eq!(console.prompt, "typing");
+    }
+
+    #[test]
+    fn history_file_dedup_and_max_entries() {
+        let path = write_script("history_save", "a\nb\n");
+        let mut console = Console::new();
+        let mut cvars = Cvars::default();
+        console.enable_persistent_history(&path, 3).unwrap();
+        for input in ["b", "c", "c", "   ", "d", "c"] {
+            run(&mut console, &mut cvars, input);
+        }
+        let saved = fs::read_to_string(&path).unwrap();
+        fs::remove_file(&path).unwrap();
+        // Only consecutive duplicates are removed.
+        assert_eq!(saved, "c\nd\nc\n");
+    }
+
+    #[test]
+    fn history_file_missing() {
+        let dir = temp_path("history_dir");
+        let path = dir.join("nested").join("history");
+        let mut console = Console::new();
+        let mut cvars = Cvars::default();
+        console.enable_persistent_history(&path, 10).unwrap();
+        console.history_back();
+        assert_eq!(console.prompt, "");
+
+        // The file and its directories are created when saving.
+        run(&mut console, &mut cvars, "g_lives 2");
+        let saved = fs::read_to_string(&path).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(saved, "g_lives 2\n");
+    }
+
+    #[test]
+    fn history_path_from_env() {
+        let path = |xdg: Option<&str>, home: Option<&str>| {
+            history_path(xdg.map(OsString::from), home.map(OsString::from), "game")
+        };
+        assert_eq!(
+            path(Some("/state"), Some("/home/me")),
+            Some(PathBuf::from("/state/game/console_history"))
+        );
+        // Relative paths in XDG_STATE_HOME are ignored.
+        assert_eq!(
+            path(Some("state"), Some("/home/me")),
+            Some(PathBuf::from("/home/me/.local/state/game/console_history"))
+        );
+        assert_eq!(
+            path(None, Some("/home/me")),
+            Some(PathBuf::from("/home/me/.local/state/game/console_history"))
+        );
+        assert_eq!(path(None, None), None);
+
+        if let Some(path) = default_history_path("game") {
+            assert!(path.ends_with("game/console_history"));
+        }
+    }
+
+    #[test]
+    fn exec_saved_config() {
+        let mut console = Console::new();
+        let saved = Cvars {
+            g_loadout: "it's;a \"weird\" // loadout\n\t\\".to_owned(),
+            g_lives: 5,
+            ..Cvars::default()
+        };
+        let path = temp_path("saved.cfg");
+        // Protected cvars can't be set from scripts.
+        let options = config::SaveOptions {
+            only_changed: true,
+            ..Default::default()
+        };
+        config::save_file(&saved, &path, options).unwrap();
+
+        let mut cvars = Cvars::default();
+        run(
+            &mut console,
+            &mut cvars,
+            &format!("exec {}", config::quote(path.to_str().unwrap())),
+        );
+        fs::remove_file(&path).unwrap();
+        assert!(console.history.iter().all(|line| line.is_input));
+        assert_eq!(cvars.g_loadout, saved.g_loadout);
+        assert_eq!(cvars.g_lives, 5);
+    }
+
+    fn complete(console: &mut Console, prompt: &str) -> Completion {
+        console.prompt = prompt.to_owned();
+        console.complete(&Cvars::default(), prompt.len())
+    }
+
+    #[test]
+    fn reset_all_skips_protected_cvars() {
+        let mut console = Console::new();
+        let mut cvars = Cvars {
+            g_armor: 50,
+            sv_gravity: 100.0,
+            sv_version: 2,
+            ..Cvars::default()
+        };
+
+        run(&mut console, &mut cvars, "reset_all");
+        assert_eq!(cvars.g_armor, 100);
+        assert_eq!(cvars.sv_gravity, 100.0);
+        assert_eq!(cvars.sv_version, 2);
+        assert_eq!(
+            last_line(&console),
+            "Skipped 2 read-only or cheat protected cvars"
+        );
+
+        console.cheats = true;
+        run(&mut console, &mut cvars, "reset_all");
+        assert_eq!(cvars.sv_gravity, 800.0);
+        assert_eq!(cvars.sv_version, 2);
+        assert_eq!(
+            last_line(&console),
+            "Skipped 1 read-only or cheat protected cvars"
+        );
+    }
+
+    #[test]
+    fn complete_cycles_through_candidates() {
+        let mut console = Console::new();
+
+        let completion = complete(&mut console, "g_l");
+        assert_eq!(completion.candidates, ["g_lives", "g_loadout"]);
+        assert_eq!(completion.index, 0);
+        assert_eq!(console.prompt, "g_lives");
+        assert_eq!(completion.cursor, 7);
+
+        let cvars = Cvars::default();
+        let completion = console.complete(&cvars, completion.cursor);
+        assert_eq!(completion.index, 1);
+        assert_eq!(console.prompt, "g_loadout");
+        let completion = console.complete(&cvars, completion.cursor);
+        assert_eq!(completion.index, 0);
+        assert_eq!(console.prompt, "g_lives");
+    }
+
+    #[test]
+    fn complete_restarts_when_prompt_or_cursor_changes() {
+        let mut console = Console::new();
+        let cvars = Cvars::default();
+
+        complete(&mut console, "g_l");
+        assert_eq!(console.prompt, "g_lives");
+
+        // Typing something else starts a new completion.
+        console.prompt = "g_a".to_owned();
+        let completion = console.complete(&cvars, 3);
+        assert_eq!(completion.candidates, ["g_armor"]);
+        assert_eq!(console.prompt, "g_armor");
+
+        // So does moving the cursor.
+        console.prompt = "g_l;g_l".to_owned();
+        console.complete(&cvars, 7);
+        assert_eq!(console.prompt, "g_l;g_lives");
+        let completion = console.complete(&cvars, 3);
+        assert_eq!(completion.index, 0);
+        assert_eq!(console.prompt, "g_lives;g_lives");
+    }
+
+    #[test]
+    fn complete_nothing() {
+        let mut console = Console::new();
+        let completion = complete(&mut console, "xyz");
+        assert!(completion.candidates.is_empty());
+        assert_eq!(completion.cursor, 3);
+        assert_eq!(console.prompt, "xyz");
+        let completion = complete(&mut console, "g_armor 1");
+        assert!(completion.candidates.is_empty());
+    }
+
+    #[test]
+    fn complete_commands_and_reset() {
+        let mut console = Console::new();
+        assert_eq!(
+            complete(&mut console, "res").candidates,
+            ["reset", "reset_all"]
+        );
+
+        let completion = complete(&mut console, "reset g_ar");
+        assert_eq!(completion.candidates, ["g_armor"]);
+        assert_eq!(console.prompt, "reset g_armor");
+    }
+
+    #[test]
+    fn complete_values() {
+        let mut console = Console::new();
+
+        let completion = complete(&mut console, "cl_fullscreen ");
+        assert_eq!(completion.candidates, ["false", "true"]);
+        assert_eq!(console.prompt, "cl_fullscreen false");
+        complete(&mut console, "cl_fullscreen t");
+        assert_eq!(console.prompt, "cl_fullscreen true");
+
+        // The values are formatted, not the source code (`Split::Horizontal`).
+        let completion = complete(&mut console, "cl_split ");
+        assert_eq!(completion.candidates, ["horizontal", "vertical"]);
+        complete(&mut console, "cl_split h");
+        assert_eq!(console.prompt, "cl_split horizontal");
+
+        assert!(complete(&mut console, "g_armor ").candidates.is_empty());
+    }
+
+    #[test]
+    fn complete_prompt_prints_candidates() {
+        let mut console = Console::new();
+        let cvars = Cvars::default();
+
+        console.prompt = "g_l".to_owned();
+        let completion = console.complete_prompt(&cvars);
+        assert_eq!(completion.index, 0);
+        assert_eq!(console.prompt, "g_lives");
+        assert_eq!(last_line(&console), "g_lives  g_loadout");
+        let len = console.history.len();
+
+        // Cycling only prints them again when it gets back to the first one.
+        console.complete_prompt(&cvars);
+        assert_eq!(console.prompt, "g_loadout");
+        assert_eq!(console.history.len(), len);
+        console.complete_prompt(&cvars);
+        assert_eq!(console.prompt, "g_lives");
+        assert_eq!(console.history.len(), len + 1);
+        let len = console.history.len();
+
+        // A single candidate isn't printed.
+        console.prompt = "g_a".to_owned();
+        console.complete_prompt(&cvars);
+        assert_eq!(console.prompt, "g_armor");
+        assert_eq!(console.history.len(), len);
+    }
+
+    #[test]
+    fn complete_after_semicolon() {
+        let mut console = Console::new();
+
+        complete(&mut console, "g_armor 1;g_a");
+        assert_eq!(console.prompt, "g_armor 1;g_armor");
+        complete(&mut console, "g_armor 1; cl_split v");
+        assert_eq!(console.prompt, "g_armor 1; cl_split vertical");
+
+        // The word ends at `;` too.
+        console.prompt = "g_a;g_lives 1".to_owned();
+        console.complete(&Cvars::default(), 3);
+        assert_eq!(console.prompt, "g_armor;g_lives 1");
+    }
+}
@@ -2,25 +2,30 @@
 #![warn(missing_docs)]
 #![allow(clippy::let_and_return)]
 
-use std::{collections::HashSet, env};
+use std::{
+    collections::{HashMap, HashSet},
+    env,
+};
 
 use proc_macro::TokenStream;
-use proc_macro2::Span;
-use quote::quote;
+use proc_macro2::{Span, TokenTree};
+use quote::{format_ident, quote, ToTokens};
 use syn::{
+    bracketed,
     parse::{Parse, ParseStream},
-    parse_macro_input,
+    parse_macro_input, parse_quote,
     punctuated::Punctuated,
-    AttrStyle, Attribute, Data, DeriveInput, Expr, Fields, Ident, Meta, MetaList, Token, Type,
+    AttrStyle, Attribute, Data, DeriveInput, Expr, ExprLit, ExprUnary, Fields, Generics, Ident,
+    Lit, Meta, MetaList, MetaNameValue, Token, Type, UnOp, Visibility,
 };
 
 /// Parsed input to the `cvars!` macro.
 struct CvarsDef {
     attrs: Vec<Attribute>,
-    /// Whether `#[cvars(skip)]` was present.
-    /// It has to be removed from the list of attributes before passing them on
-    /// so we save it here separately.
-    sorted: bool,
+    /// Options from `#[cvars(...)]`.
+    /// They have to be removed from the list of attributes before passing them on
+    /// so we save them here separately.
+    options: StructOptions,
     cvars: Vec<CvarDef>,
 }
 
@@ -28,48 +33,82 @@ impl Parse for CvarsDef {
     fn parse(input: ParseStream) -> syn::Result<Self> {
         let attrs_raw = input.call(Attribute::parse_inner)?;
         let mut attrs = Vec::new();
-        let mut sorted = false;
+        let mut options = StructOptions::default();
         for attr in attrs_raw {
-            if is_sorted(&attr) {
-                sorted = true;
-            } else {
+            if !parse_struct_attr(&attr, &mut options)? {
                 attrs.push(attr);
             }
         }
 
         let punctuated = Punctuated::<CvarDef, Token![,]>::parse_terminated(input)?;
-        let cvars = punctuated.into_iter().collect();
+        let mut cvars = Vec::new();
+        for cvar_def in punctuated {
+            cvars.extend(cvar_def.expand_cfg_attrs()?);
+        }
 
         Ok(CvarsDef {
             attrs,
-            sorted,
+            options,
             cvars,
         })
     }
 }
 
+/// Options which apply to the whole struct, set using `#[cvars(...)]` on the struct.
+#[derive(Default)]
+struct StructOptions {
+    /// `#[cvars(sorted)]` - check the cvars are sorted.
+    sorted: bool,
+    /// `#[cvars(serde)]` - generate `Serialize` and `Deserialize` impls.
+    /// All cvar types have to impl `Serialize` and `Deserialize`.
+    serde: bool,
+    /// `#[cvars(lookup_table)]` - find cvars by binary search in a sorted table instead of matching on strings.
+    lookup_table: bool,
+    /// `#[cvars(name_enum)]` or `#[cvars(name_enum = ...)]` - generate an enum with a variant for each cvar.
+    name_enum: Option<Ident>,
+    /// `#![cvars(name = ...)]` - name of the struct generated by `cvars!`.
+    name: Option<Ident>,
+    /// `#![cvars(vis = ...)]` - visibility of the struct generated by `cvars!`.
+    vis: Option<Visibility>,
+}
+
+/// Options which apply to one field, set using `#[cvars(...)]` on the field.
+#[derive(Default)]
+struct FieldOptions {
+    /// `#[cvars(skip)]` - the field is not a cvar.
+    skip: bool,
+    /// `#[cvars(observers)]` - the field holds the observers, it's not a cvar either.
+    observers: bool,
+    /// `#[cvars(nested)]` - the field is a struct containing more cvars.
+    nested: bool,
+    /// `#[cvars(min = ...)]` - the smallest allowed value.
+    min: Option<Expr>,
+    /// `#[cvars(max = ...)]` - the largest allowed value.
+    max: Option<Expr>,
+    /// `#[cvars(one_of = [...])]` - the list of allowed values.
+    one_of: Option<Vec<Expr>>,
+    /// `#[cvars(validate = ...)]` - a function which checks the new value.
+    validate: Option<Expr>,
+    /// `#[cvars(flags(...))]` - names of the `CvarFlags` fields to set.
+    flags: Vec<Ident>,
+    /// `#[cfg(...)]` - not a cvars option, it's copied to all code generated for the field.
+    cfgs: Vec<Attribute>,
+}
+
 /// Definition of one cvar from the `cvars!` macro.
 struct CvarDef {
     attrs: Vec<Attribute>,
-    /// Whether `#[cvars(skip)]` was present.
-    skip: bool,
+    /// Options from `#[cvars(...)]`.
+    options: FieldOptions,
     name: Ident,
     ty: Type,
     value: Expr,
 }
 
 impl Parse for CvarDef {
+    /// Parse the cvar but not its attributes, that's done by `expand_cfg_attrs`.
     fn parse(input: ParseStream) -> syn::Result<Self> {
-        let attrs_raw = input.call(Attribute::parse_outer)?;
-        let mut attrs = Vec::new();
-        let mut skip = false;
-        for attr in attrs_raw {
-            if is_skip(&attr) {
-                skip = true;
-            } else {
-                attrs.push(attr);
-            }
-        }
+        let attrs = input.call(Attribute::parse_outer)?;
         let name = input.parse()?;
         let _: Token![:] = input.parse()?;
         let ty = input.parse()?;
@@ -77,7 +116,7 @@ impl Parse for CvarDef {
         let value = input.parse()?;
         Ok(CvarDef {
             attrs,
-            skip,
+            options: FieldOptions::default(),
             name,
             ty,
             value,
@@ -85,38 +124,239 @@ impl Parse for CvarDef {
     }
 }
 
-/// Is it `cvars(sorted)`?
-fn is_sorted(attr: &Attribute) -> bool {
-    if let Meta::List(MetaList { path, tokens, .. }) = &attr.meta {
-        if !path.is_ident("cvars") {
-            return false;
+impl CvarDef {
+    /// Parse the `#[cvars(...)]` attributes into options.
+    ///
+    /// The macro can't evaluate `#[cfg_attr(predicate, cvars(...))]` so the cvar is split
+    /// into one version with `#[cfg(predicate)]` and the options
+    /// and one with `#[cfg(not(predicate))]` without them.
+    fn expand_cfg_attrs(self) -> syn::Result<Vec<CvarDef>> {
+        let mut defs = Vec::new();
+        for attrs_raw in expand_cfg_attrs(&self.attrs)? {
+            let mut attrs = Vec::new();
+            let mut options = FieldOptions::default();
+            for attr in attrs_raw {
+                if !parse_field_attr(&attr, &mut options)? {
+                    attrs.push(attr);
+                }
+            }
+            options.cfgs = get_cfgs(&attrs);
+            defs.push(CvarDef {
+                attrs,
+                options,
+                name: self.name.clone(),
+                ty: self.ty.clone(),
+                value: self.value.clone(),
+            });
         }
+        Ok(defs)
+    }
+}
 
-        if tokens.to_string() == "sorted" {
-            return true;
-        } else {
-            panic!("Unknown cvars attribute: {}", tokens);
+/// Return all the versions of the attribute list with `cfg_attr`s containing `cvars(...)` expanded.
+///
+/// Other `cfg_attr`s are left for the compiler.
+fn expand_cfg_attrs(attrs: &[Attribute]) -> syn::Result<Vec<Vec<Attribute>>> {
+    let mut versions = vec![Vec::new()];
+    for attr in attrs {
+        let Some((predicate, inner)) = parse_cfg_attr(attr)? else {
+            for version in &mut versions {
+                version.push(attr.clone());
+            }
+            continue;
+        };
+        if !contains_cvars_attr(&inner)? {
+            for version in &mut versions {
+                version.push(attr.clone());
+            }
+            continue;
+        }
+
+        let inner_versions = expand_cfg_attrs(&inner)?;
+        let mut expanded = Vec::new();
+        for version in versions {
+            for inner_version in &inner_versions {
+                let mut enabled = version.clone();
+                enabled.push(parse_quote! { #[cfg(#predicate)] });
+                enabled.extend(inner_version.iter().cloned());
+                expanded.push(enabled);
+            }
+            let mut disabled = version;
+            disabled.push(parse_quote! { #[cfg(not(#predicate))] });
+            expanded.push(disabled);
         }
+        versions = expanded;
+    }
+    Ok(versions)
+}
+
+/// If it's a `cfg_attr(predicate, attrs...)` attribute, return the predicate and the attributes.
+fn parse_cfg_attr(attr: &Attribute) -> syn::Result<Option<(Meta, Vec<Attribute>)>> {
+    if !attr.path().is_ident("cfg_attr") {
+        return Ok(None);
     }
+    attr.parse_args_with(|input: ParseStream| {
+        let predicate = input.parse()?;
+        let _: Token![,] = input.parse()?;
+        let metas = Punctuated::<Meta, Token![,]>::parse_terminated(input)?;
+        let inner = metas
+            .into_iter()
+            .map(|meta| parse_quote! { #[#meta] })
+            .collect();
+        Ok(Some((predicate, inner)))
+    })
+}
 
-    false
+/// Whether any of the attributes is `cvars(...)`, possibly inside another `cfg_attr`.
+fn contains_cvars_attr(attrs: &[Attribute]) -> syn::Result<bool> {
+    for attr in attrs {
+        if attr.path().is_ident("cvars") {
+            return Ok(true);
+        }
+        if let Some((_, inner)) = parse_cfg_attr(attr)? {
+            if contains_cvars_attr(&inner)? {
+                return Ok(true);
+            }
+        }
+    }
+    Ok(false)
 }
 
-/// Is it `cvars(skip)`?
-fn is_skip(attr: &Attribute) -> bool {
-    if let Meta::List(MetaList { path, tokens, .. }) = &attr.meta {
+/// If it's a `cvars(...)` attribute, save the options it contains and return true.
+fn parse_struct_attr(attr: &Attribute, options: &mut StructOptions) -> syn::Result<bool> {
+    if let Meta::List(MetaList { path, .. }) = &attr.meta {
         if !path.is_ident("cvars") {
-            return false;
+            return Ok(false);
         }
 
-        if tokens.to_string() == "skip" {
-            return true;
-        } else {
-            panic!("Unknown cvars attribute: {}", tokens);
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("sorted") {
+                options.sorted = true;
+            } else if meta.path.is_ident("serde") {
+                if !cfg!(feature = "serde") {
+                    return Err(meta.error("`cvars(serde)` requires the `serde` feature of cvars"));
+                }
+                options.serde = true;
+            } else if meta.path.is_ident("lookup_table") {
+                options.lookup_table = true;
+            } else if meta.path.is_ident("name_enum") {
+                let name = if meta.input.peek(Token![=]) {
+                    meta.value()?.parse()?
+                } else {
+                    Ident::new("CvarName", Span::call_site())
+                };
+                options.name_enum = Some(name);
+            } else if meta.path.is_ident("name") {
+                options.name = Some(meta.value()?.parse()?);
+            } else if meta.path.is_ident("vis") {
+                options.vis = Some(meta.value()?.parse()?);
+            } else {
+                return Err(meta.error(format!(
+                    "unknown cvars attribute `{}`",
+                    meta.path.to_token_stream()
+                )));
+            }
+            Ok(())
+        })?;
+        return Ok(true);
+    }
+
+    Ok(false)
+}
+
+/// If it's a `cvars(...)` attribute on a field, save the options it contains and return true.
+fn parse_field_attr(attr: &Attribute, options: &mut FieldOptions) -> syn::Result<bool> {
+    if let Meta::List(MetaList { path, .. }) = &attr.meta {
+        if !path.is_ident("cvars") {
+            return Ok(false);
         }
+
+        attr.parse_nested_meta(|meta| {
+            if meta.path.is_ident("skip") {
+                options.skip = true;
+            } else if meta.path.is_ident("observers") {
+                options.observers = true;
+            } else if meta.path.is_ident("nested") {
+                options.nested = true;
+            } else if meta.path.is_ident("min") {
+                options.min = Some(meta.value()?.parse()?);
+            } else if meta.path.is_ident("max") {
+                options.max = Some(meta.value()?.parse()?);
+            } else if meta.path.is_ident("one_of") {
+                let value = meta.value()?;
+                let content;
+                bracketed!(content in value);
+                let allowed = Punctuated::<Expr, Token![,]>::parse_terminated(&content)?;
+                options.one_of = Some(allowed.into_iter().collect());
+            } else if meta.path.is_ident("validate") {
+                options.validate = Some(meta.value()?.parse()?);
+            } else if meta.path.is_ident("flags") {
+                meta.parse_nested_meta(|flag| {
+                    const FLAGS: [&str; 5] =
+                        ["cheat", "read_only", "archive", "replicated", "user_info"];
+                    match flag.path.get_ident() {
+                        Some(ident) if FLAGS.iter().any(|f| ident == f) => {
+                            options.flags.push(ident.clone());
+                        }
+                        _ => {
+                            return Err(flag.error(format!(
+                                "unknown cvar flag `{}`, expected one of: {}",
+                                flag.path.to_token_stream(),
+                                FLAGS.join(", ")
+                            )));
+                        }
+                    }
+                    Ok(())
+                })?;
+            } else {
+                return Err(meta.error(format!(
+                    "unknown cvars attribute `{}`",
+                    meta.path.to_token_stream()
+                )));
+            }
+            Ok(())
+        })?;
+        return Ok(true);
     }
 
-    false
+    Ok(false)
+}
+
+/// Get the `#[cfg(...)]` attributes from a list of attributes.
+///
+/// `#[derive(SetGet)]` never sees them because fields disabled by `cfg` are removed
+/// and `cfg_attr`s are expanded before derives run but `cvars!` has to handle them itself.
+fn get_cfgs(attrs: &[Attribute]) -> Vec<Attribute> {
+    attrs
+        .iter()
+        .filter(|attr| attr.path().is_ident("cfg"))
+        .cloned()
+        .collect()
+}
+
+/// Get the doc comment from a list of attributes.
+///
+/// Each line of a doc comment is a separate `#[doc = "..."]` attribute.
+fn get_doc(attrs: &[Attribute]) -> String {
+    let mut lines = Vec::new();
+    for attr in attrs {
+        if let Meta::NameValue(MetaNameValue {
+            path,
+            value: Expr::Lit(ExprLit {
+                lit: Lit::Str(lit), ..
+            }),
+            ..
+        }) = &attr.meta
+        {
+            if path.is_ident("doc") {
+                // Doc comments usually have a space after the slashes, remove it.
+                let line = lit.value();
+                let line = line.strip_prefix(' ').unwrap_or(&line).to_owned();
+                lines.push(line);
+            }
+        }
+    }
+    lines.join("\n")
 }
 
 /// Generate the `Cvars` struct and its impls. Each cvar and its default value is defined on one line.
@@ -135,7 +375,21 @@ fn is_skip(attr: &Attribute) -> bool {
 /// - `get_string` - take cvar name as string and return its value as a `String`
 /// - `set_str` - take cvar name as string and its new value as a `&str`
 /// - `get` - take cvar name as string and return its value as the correct type
+/// - `get_ref`, `get_mut` - like `get` but return a reference instead of cloning the value
+/// - `get_any`, `get_any_mut` - take cvar name as string and return a reference to the cvar as `dyn Any`
 /// - `set` - take cvar name as string and its new value as the correct type
+/// - `get_numeric`, `set_numeric` - like `get` and `set` but convert between numeric types
+/// - `get_key`, `set_key`, `set_str_key` - like `get`, `set` and `set_str` but take a typed key instead of a string
+/// - `reset`, `reset_all` - set one or all cvars back to their default values
+/// - `reset_where` - set cvars whose flags match a filter back to their default values
+/// - `changed` - list cvars whose values differ from their defaults
+/// - `cvar_infos` - return information about all cvars (name, type, default value, constraints, flags, doc comment)
+/// - `allowed_values` - take cvar name as string and return the values from `one_of` formatted as strings
+/// - `cvar_range` - take cvar name as string and return the evaluated `min` and `max` as `f64`
+/// - `observers`, `observers_mut` - access the field marked with `#[cvars(observers)]` if there is one
+///
+/// It also generates a typed key for each cvar as an associated constant,
+/// e.g. `Cvars::G_DAMAGE` for `g_damage`, see [`CvarKey`](https://docs.rs/cvars/latest/cvars/struct.CvarKey.html).
 ///
 /// See your IDE or [the SetGet trait](https://docs.rs/cvars/latest/cvars/trait.SetGet.html)
 /// for their exact signatures.
@@ -171,7 +425,47 @@ fn is_skip(attr: &Attribute) -> bool {
 /// ```
 ///
 /// Use `#![cvars(sorted)]` to check the cvars are in lexicographic order.
-/// If not, the macro will panic as there's currently no way to emit a warning from proc macros.
+/// If not, the macro will emit an error as there's currently no way to emit a warning from proc macros.
+///
+/// `#[cfg(...)]` on a cvar applies to all the code generated for it,
+/// for example to have debug-only or editor-only cvars in the same struct.
+/// A cvar can even be defined multiple times if the `cfg`s ensure only one of them is enabled.
+/// `#[cfg_attr(...)]` works too, including with `cvars(...)` inside it.
+///
+/// ```rust
+/// use cvars::cvars;
+///
+/// cvars! {
+///     g_gravity: f32 = 800.0,
+///     #[cfg(debug_assertions)]
+///     dbg_draw_hitboxes: bool = false,
+///     #[cfg_attr(not(debug_assertions), cvars(flags(cheat)))]
+///     g_god_mode: bool = false,
+/// }
+/// ```
+///
+/// # Name and visibility
+///
+/// The generated struct is called `Cvars` and is `pub` by default.
+/// Use `#![cvars(name = ..., vis = ...)]` to change them,
+/// for example to have multiple cvar structs in one module.
+///
+/// ```rust
+/// use cvars::cvars;
+///
+/// cvars! {
+///     #![cvars(name = ClientCvars, vis = pub(crate))]
+///     cl_fov: f32 = 90.0,
+/// }
+///
+/// cvars! {
+///     #![cvars(name = ServerCvars)]
+///     sv_hostname: String = "Unnamed".to_owned(),
+/// }
+///
+/// let cl = ClientCvars::default();
+/// let sv = ServerCvars::default();
+/// ```
 #[proc_macro]
 pub fn cvars(input: TokenStream) -> TokenStream {
     let begin = std::time::Instant::now();
@@ -184,26 +478,52 @@ pub fn cvars(input: TokenStream) -> TokenStream {
     }
 
     let mut attrss = Vec::new();
-    let mut skips = Vec::new();
+    let mut cfgss = Vec::new();
+    let mut field_options = Vec::new();
     let mut names = Vec::new();
     let mut tys = Vec::new();
     let mut values = Vec::new();
+    let mut docs = Vec::new();
     for cvar_def in cvars_def.cvars {
+        docs.push(get_doc(&cvar_def.attrs));
         attrss.push(cvar_def.attrs);
-        skips.push(cvar_def.skip);
+        cfgss.push(cvar_def.options.cfgs.clone());
+        field_options.push(cvar_def.options);
         names.push(cvar_def.name);
         tys.push(cvar_def.ty);
         values.push(cvar_def.value);
     }
 
-    let struct_name = Ident::new("Cvars", Span::call_site());
-    let generated = generate(struct_name, cvars_def.sorted, &skips, &names, &tys);
+    let struct_name = cvars_def
+        .options
+        .name
+        .clone()
+        .unwrap_or_else(|| Ident::new("Cvars", Span::call_site()));
+    let vis = cvars_def
+        .options
+        .vis
+        .clone()
+        .unwrap_or_else(|| parse_quote! { pub });
+    let generated = generate(GenerateInput {
+        struct_name: struct_name.clone(),
+        generics: &Generics::default(),
+        options: &cvars_def.options,
+        field_options: &field_options,
+        names: &names,
+        tys: &tys,
+        values: Some(&values),
+        docs: &docs,
+    });
+    let generated = match generated {
+        Ok(generated) => generated,
+        Err(err) => return err.into_compile_error().into(),
+    };
 
     let expanded = quote! {
         #(
             #cvars_attrs
         )*
-        pub struct Cvars {
+        #vis struct #struct_name {
             #(
                 #( #attrss )*
                 pub #names: #tys,
@@ -211,10 +531,13 @@ pub fn cvars(input: TokenStream) -> TokenStream {
         }
 
         #[automatically_derived]
-        impl ::core::default::Default for Cvars {
+        impl ::core::default::Default for #struct_name {
             fn default() -> Self {
                 Self {
-                    #( #names: #values, )*
+                    #(
+                        #( #cfgss )*
+                        #names: #values,
+                    )*
                 }
             }
         }
@@ -234,10 +557,18 @@ pub fn cvars(input: TokenStream) -> TokenStream {
 /// Generate setters and getters that take cvar names as string.
 /// This does the same thing as `cvars!` but you can use it on an existing struct.
 ///
-/// Initial/default values have to be specified separately.
+/// Initial/default values have to be specified separately by implementing `Default` for the struct.
+/// They're used by methods like `reset`, `changed` and `default_string`
+/// which compare cvars to or set them back to their default values.
+/// The struct **must** impl `Default`, otherwise the generated code doesn't compile.
+/// This is a breaking change compared to cvars 0.4 where `Default` was optional.
 ///
 /// All types used as cvars have to impl `FromStr` and `Display`.
 ///
+/// Generic structs are supported. The generated impls require the cvar types
+/// to impl `FromStr`, `Display` and `Any` (which means they have to be `'static`).
+/// Fields with other types (e.g. references) have to be marked with `#[cvars(skip)]`.
+///
 /// See [`cvars!`](cvars!#generated-code) for more details about the generated code.
 ///
 /// # Example
@@ -251,8 +582,8 @@ pub fn cvars(input: TokenStream) -> TokenStream {
 ///     g_rocket_launcher_damage: f32,
 /// }
 ///
-/// impl Cvars {
-///     pub fn new() -> Self {
+/// impl Default for Cvars {
+///     fn default() -> Self {
 ///         Self {
 ///             g_rocket_launcher_ammo_max: 20,
 ///             g_rocket_launcher_damage: 75.0,
@@ -266,164 +597,931 @@ pub fn derive(input: TokenStream) -> TokenStream {
 
     let input: DeriveInput = parse_macro_input!(input);
 
+    let expanded = derive_set_get(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into();
+
+    let end = std::time::Instant::now();
+    if env::var("CVARS_STATS").is_ok() {
+        eprintln!("derive(SetGet) took {:?}", end - begin);
+    }
+
+    expanded
+}
+
+fn derive_set_get(input: DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
     let struct_name = input.ident;
     let named_fields = match input.data {
         Data::Struct(struct_data) => match struct_data.fields {
             Fields::Named(named_fields) => named_fields,
-            Fields::Unnamed(_) => panic!("tuple structs are not supported, use named fields"),
-            Fields::Unit => panic!("unit structs are not supported, use curly braces"),
+            Fields::Unnamed(fields) => {
+                return Err(syn::Error::new_spanned(
+                    fields,
+                    "tuple structs are not supported, use named fields",
+                ));
+            }
+            Fields::Unit => {
+                return Err(syn::Error::new_spanned(
+                    struct_name,
+                    "unit structs are not supported, use curly braces",
+                ));
+            }
         },
-        Data::Enum(_) => panic!("enums are not supported, use a struct"),
-        Data::Union(_) => panic!("unions are not supported, use a struct"),
+        Data::Enum(data) => {
+            return Err(syn::Error::new_spanned(
+                data.enum_token,
+                "enums are not supported, use a struct",
+            ));
+        }
+        Data::Union(data) => {
+            return Err(syn::Error::new_spanned(
+                data.union_token,
+                "unions are not supported, use a struct",
+            ));
+        }
     };
-    let sorted = input.attrs.iter().any(is_sorted);
+    let mut options = StructOptions::default();
+    for attr in &input.attrs {
+        parse_struct_attr(attr, &mut options)?;
+    }
+    let name_tokens = options.name.as_ref().map(ToTokens::to_token_stream);
+    let vis_tokens = options.vis.as_ref().map(ToTokens::to_token_stream);
+    if let Some(tokens) = name_tokens.or(vis_tokens) {
+        return Err(syn::Error::new_spanned(
+            tokens,
+            "`cvars(name = ...)` and `cvars(vis = ...)` can only be used with `cvars!`, not `#[derive(SetGet)]`",
+        ));
+    }
+    // Items generated next to the struct (e.g. the name enum) get the same visibility.
+    options.vis = Some(input.vis);
 
-    // Get the list of all cvars and their types
-    let mut skips = Vec::new();
+    // Get the list of all cvars and their types.
+    // Report errors in all fields' attributes at once instead of making the user fix them one by one.
+    let mut errors: Option<syn::Error> = None;
+    let mut field_options = Vec::new();
     let mut names = Vec::new();
     let mut tys = Vec::new();
+    let mut docs = Vec::new();
     for field in named_fields.named {
-        let contains_skip = field.attrs.iter().any(is_skip);
-        skips.push(contains_skip);
+        let mut opts = FieldOptions::default();
+        for attr in &field.attrs {
+            if let Err(err) = parse_field_attr(attr, &mut opts) {
+                match &mut errors {
+                    Some(errors) => errors.combine(err),
+                    None => errors = Some(err),
+                }
+            }
+        }
+        opts.cfgs = get_cfgs(&field.attrs);
+        field_options.push(opts);
         let name = field.ident.expect("unreachable: ident was None");
         names.push(name);
         tys.push(field.ty);
+        docs.push(get_doc(&field.attrs));
     }
-
-    let expanded = generate(struct_name, sorted, &skips, &names, &tys);
-    let expanded = expanded.into();
-
-    let end = std::time::Instant::now();
-    if env::var("CVARS_STATS").is_ok() {
-        eprintln!("derive(SetGet) took {:?}", end - begin);
+    if let Some(errors) = errors {
+        return Err(errors);
     }
 
-    expanded
+    generate(GenerateInput {
+        struct_name,
+        generics: &input.generics,
+        options: &options,
+        field_options: &field_options,
+        names: &names,
+        tys: &tys,
+        values: None,
+        docs: &docs,
+    })
 }
 
-fn generate(
+/// The struct and its fields as parsed by `cvars!` or `#[derive(SetGet)]`.
+///
+/// The slices have one item per field, including skipped and nested fields.
+struct GenerateInput<'a> {
     struct_name: Ident,
-    sorted: bool,
-    skips: &[bool],
-    names_all: &[Ident],
-    tys_all: &[Type],
-) -> proc_macro2::TokenStream {
+    generics: &'a Generics,
+    options: &'a StructOptions,
+    field_options: &'a [FieldOptions],
+    names: &'a [Ident],
+    tys: &'a [Type],
+    /// The default values, only known when using `cvars!`.
+    values: Option<&'a [Expr]>,
+    docs: &'a [String],
+}
+
+fn generate(input: GenerateInput) -> syn::Result<proc_macro2::TokenStream> {
+    let GenerateInput {
+        struct_name,
+        generics,
+        options,
+        field_options,
+        names: names_all,
+        tys: tys_all,
+        values: values_all,
+        docs: docs_all,
+    } = input;
     let mut names = Vec::new();
     let mut tys = Vec::new();
-    for i in 0..skips.len() {
-        if skips[i] {
+    let mut values = Vec::new();
+    let mut docs = Vec::new();
+    let mut opts = Vec::new();
+    let mut observers = None;
+    let mut nested = Vec::new();
+    let mut nested_tys = Vec::new();
+    // The `#[cfg(...)]` attributes of each cvar and nested struct.
+    // They're put on every item, statement, match arm, etc. generated for the field.
+    let mut cfgs = Vec::new();
+    let mut nested_cfgs = Vec::new();
+    // Statements which build the list of all cvar infos in the same order as the fields.
+    let mut infos_parts = Vec::new();
+    for i in 0..field_options.len() {
+        let field_cfgs = &field_options[i].cfgs;
+        let cfg = quote! { #( #field_cfgs )* };
+        if field_options[i].observers {
+            if observers.is_some() {
+                return Err(syn::Error::new_spanned(
+                    &names_all[i],
+                    "only one field can be marked with `cvars(observers)`",
+                ));
+            }
+            if let Some(field_cfg) = field_cfgs.first() {
+                return Err(syn::Error::new_spanned(
+                    field_cfg,
+                    "`cfg` is not supported on the field marked with `cvars(observers)`",
+                ));
+            }
+            observers = Some(&names_all[i]);
+            continue;
+        }
+        if field_options[i].skip {
+            continue;
+        }
+        if field_options[i].nested {
+            let name = &names_all[i];
+            let ty = &tys_all[i];
+            infos_parts.push(quote! {
+                #cfg
+                for info in <#ty>::__cvar_infos() {
+                    infos.push(info.prefixed(stringify!(#name)));
+                }
+            });
+            nested.push(name);
+            nested_tys.push(ty);
+            nested_cfgs.push(cfg);
             continue;
         }
 
+        // Indexing into CVAR_INFOS wouldn't work if some cvars are disabled by `cfg`.
+        infos_parts.push(quote! {
+            #cfg
+            infos.push(*direct_infos.next().unwrap());
+        });
+        cfgs.push(cfg);
         names.push(&names_all[i]);
         tys.push(&tys_all[i]);
+        values.push(values_all.map(|values_all| &values_all[i]));
+        docs.push(&docs_all[i]);
+        opts.push(&field_options[i]);
+    }
+
+    if !field_options.is_empty() && names.is_empty() && nested.is_empty() {
+        return Err(syn::Error::new_spanned(
+            &struct_name,
+            "all fields are skipped, there has to be at least one cvar",
+        ));
+    }
+
+    // Nested structs are accessed by their field names as prefixes so they share the namespace with cvars.
+    // Fields with `cfg` are not checked, there can be multiple versions of a cvar for different configurations.
+    let mut seen = HashSet::new();
+    let fields = names
+        .iter()
+        .zip(&cfgs)
+        .chain(nested.iter().zip(&nested_cfgs));
+    for (&name, cfg) in fields {
+        if !cfg.is_empty() {
+            continue;
+        }
+        if !seen.insert(name) {
+            return Err(syn::Error::new_spanned(
+                name,
+                format!("duplicate cvar name `{}`", name),
+            ));
+        }
     }
 
-    if sorted {
-        for pair in names.windows(2) {
-            if pair[0] >= pair[1] {
+    if options.sorted {
+        for (pair, cfg_pair) in names.windows(2).zip(cfgs.windows(2)) {
+            // Multiple versions of a cvar for different configurations are allowed.
+            let versions = pair[0] == pair[1] && !cfg_pair[0].is_empty() && !cfg_pair[1].is_empty();
+            if pair[0] >= pair[1] && !versions {
                 // LATER A warning would make much more sense but it requires nightly for now:
                 // https://github.com/rust-lang/rust/issues/54140
-                panic!("cvars not sorted: `{}` >= `{}`", pair[0], pair[1]);
+                return Err(syn::Error::new_spanned(
+                    pair[1],
+                    format!("cvars not sorted: `{}` >= `{}`", pair[0], pair[1]),
+                ));
             }
         }
     }
 
-    let cvar_count = names.len();
+    // Typed keys are named like the cvars but in uppercase like all constants.
+    let mut keys = Vec::new();
+    for (&name, cfg) in names.iter().zip(&cfgs) {
+        let upper = name.to_string().trim_start_matches("r#").to_uppercase();
+        if upper == "CVAR_COUNT" || upper == "CVAR_INFOS" {
+            return Err(syn::Error::new_spanned(
+                name,
+                format!(
+                    "the key of cvar `{name}` would clash with the generated constant `{upper}`"
+                ),
+            ));
+        }
+        let key = format_ident!("{}", upper, span = name.span());
+        if cfg.is_empty() && keys.contains(&key) {
+            return Err(syn::Error::new_spanned(
+                name,
+                format!(
+                    "the key of cvar `{name}` would clash with the key of another cvar: `{upper}`"
+                ),
+            ));
+        }
+        keys.push(key);
+    }
 
-    let set_get_impl = impl_set_get(&struct_name);
+    // By default the generated methods match on the cvar name directly.
+    // With a lookup table, the name is first turned into the cvar's index using binary search
+    // and then the methods match on the index. A match on integers compiles to a jump table
+    // which is faster at runtime and generates a lot less code than hundreds of string comparisons.
+    // With a name enum, the name is parsed into the enum (using either of the two methods)
+    // and the methods match on the variants.
+    // Each pattern starts with the cvar's `cfg` attributes so they apply to the whole match arm.
+    let indices: Vec<_> = (0..names.len()).map(|index| quote! { #index }).collect();
+    let index_fn = if options.lookup_table {
+        lookup_table_fn(
+            &format_ident!("__cvar_index"),
+            &quote! { usize },
+            &names,
+            &indices,
+            &cfgs,
+        )
+    } else {
+        quote! {}
+    };
+    let (scrutinee, patterns, lookup_fn, name_enum): (_, Vec<_>, _, _) =
+        if let Some(enum_name) = &options.name_enum {
+            let variants = enum_variants(&names, &cfgs)?;
+            let vis = options.vis.clone().unwrap_or_else(|| parse_quote! { pub });
+            let name_enum = impl_name_enum(
+                &struct_name,
+                enum_name,
+                &vis,
+                &names,
+                &variants,
+                &cfgs,
+                options.lookup_table,
+            );
+            let patterns = variants
+            .iter()
+            .zip(&cfgs)
+            .map(
+                |(variant, cfg)| quote! { #cfg ::core::option::Option::Some(#enum_name::#variant) },
+            )
+            .collect();
+            // `cvar_info` still uses the index table.
+            (
+                quote! { #enum_name::__from_name(cvar_name) },
+                patterns,
+                index_fn,
+                name_enum,
+            )
+        } else if options.lookup_table {
+            let patterns = indices
+                .iter()
+                .zip(&cfgs)
+                .map(|(index, cfg)| quote! { #cfg ::core::option::Option::Some(#index) })
+                .collect();
+            (
+                quote! { Self::__cvar_index(cvar_name) },
+                patterns,
+                index_fn,
+                quote! {},
+            )
+        } else {
+            let patterns = names
+                .iter()
+                .zip(&cfgs)
+                .map(|(name, cfg)| quote! { #cfg stringify!(#name) })
+                .collect();
+            (quote! { cvar_name }, patterns, quote! {}, quote! {})
+        };
 
-    // Get the set of types used as cvars.
-    // We need to impl SetGetType for them and it needs to be done
-    // once per type, not once per cvar.
-    // Note: I benchmarked this against FnvHashSet and it doesn't make a difference.
-    let unique_tys: HashSet<_> = tys.iter().collect();
-    let mut trait_impls = Vec::new();
-    for unique_ty in unique_tys {
-        let mut getter_arms = Vec::new();
-        let mut setter_arms = Vec::new();
+    let find_info = quote! { self.cvar_infos().iter().find(|info| info.name == cvar_name) };
+    let cvar_info_body = if options.lookup_table {
+        // The index is the position among all cvar definitions
+        // but `CVAR_INFOS` doesn't contain the ones disabled by `cfg`.
+        let position = if opts.iter().all(|opts| opts.cfgs.is_empty()) {
+            quote! { index }
+        } else {
+            let enabled = opts
+                .iter()
+                .map(|opts| {
+                    if opts.cfgs.is_empty() {
+                        Ok(quote! { true })
+                    } else {
+                        let predicate = cfg_predicate(&opts.cfgs)?;
+                        Ok(quote! { cfg!(#predicate) })
+                    }
+                })
+                .collect::<syn::Result<Vec<_>>>()?;
+            let count = names.len();
+            quote! {
+                {
+                    const POSITIONS: [usize; #count] = {
+                        let enabled = [ #( #enabled, )* ];
+                        let mut positions = [0; #count];
+                        let mut position = 0;
+                        let mut index = 0;
+                        while index < #count {
+                            positions[index] = position;
+                            if enabled[index] {
+                                position += 1;
+                            }
+                            index += 1;
+                        }
+                        positions
+                    };
+                    POSITIONS[index]
+                }
+            }
+        };
+        quote! {
+            // Nested cvars have prefixed names which are only in `cvar_infos`.
+            if cvar_name.contains('.') {
+                return #find_info;
+            }
+            Self::__cvar_index(cvar_name).map(|index| &Self::CVAR_INFOS[#position])
+        }
+    } else {
+        find_info
+    };
 
-        for i in 0..names.len() {
-            let field = names[i];
-            let ty = tys[i];
-            // Each `impl SetGetType for X` block only generates match arms for cvars of type X
-            // so that the getters and setters typecheck.
-            if ty == *unique_ty {
-                let getter_arm = quote! {
-                    stringify!(#field) => ::core::result::Result::Ok(cvars.#field.clone()),
-                };
-                getter_arms.push(getter_arm);
+    // Written as a block so that nested structs can be disabled using `cfg`.
+    let cvar_count = quote! {
+        {
+            let mut count = Self::CVAR_INFOS.len();
+            #(
+                #nested_cfgs
+                {
+                    count += <#nested_tys>::CVAR_COUNT;
+                }
+            )*
+            count
+        }
+    };
 
-                let setter_arm = quote! {
-                    stringify!(#field) => ::core::result::Result::Ok(cvars.#field = value),
-                };
-                setter_arms.push(setter_arm);
-            }
-        }
-
-        // LATER Is there a sane way to automatically convert? (even fallibly)
-        //       e.g. integers default to i32 even though cvar type is usize
-        //       At the very least, it should suggest specifying the type.
-        let trait_impl = quote! {
-            #[automatically_derived]
-            impl SetGetType for #unique_ty {
-                fn get(cvars: &Cvars, cvar_name: &str) -> ::core::result::Result<Self, String> {
-                    match cvar_name {
-                        #( #getter_arms )*
-                        _ => ::core::result::Result::Err(format!(
-                            "Cvar named {} with type {} not found",
-                            cvar_name,
-                            stringify!(#unique_ty)
-                        )),
-                    }
+    // Nested cvars have prefixed names which are not known at compile time
+    // so the list of all infos is built on first use and cached.
+    // Most structs don't have nested cvars so they just use the const table.
+    let all_infos = if nested.is_empty() {
+        quote! { Self::CVAR_INFOS }
+    } else {
+        quote! {
+            static INFOS: ::std::sync::OnceLock<::std::vec::Vec<::cvars::CvarInfo>> = ::std::sync::OnceLock::new();
+            INFOS.get_or_init(|| {
+                let mut infos = ::std::vec::Vec::new();
+                let mut direct_infos = Self::CVAR_INFOS.iter();
+                #( #infos_parts )*
+                debug_assert!(direct_infos.next().is_none());
+                infos
+            })
+        }
+    };
+
+    // Names with a dot are routed into the nested struct whose field name matches the part before the dot.
+    // Direct cvars can't contain dots so they're only looked up if there's none.
+    let route = |direct: proc_macro2::TokenStream,
+                 nested_call: &dyn Fn(&Ident) -> proc_macro2::TokenStream| {
+        if nested.is_empty() {
+            return direct;
+        }
+        let arms = nested.iter().zip(&nested_cfgs).map(|(name, cfg)| {
+            let call = nested_call(name);
+            quote! {
+                #cfg
+                stringify!(#name) => #call.map_err(|err| err.prefixed(prefix)),
+            }
+        });
+        quote! {
+            if let ::core::option::Option::Some((prefix, rest)) = cvar_name.split_once('.') {
+                match prefix {
+                    #( #arms )*
+                    _ => ::core::result::Result::Err(::cvars::CvarError::NotFound {
+                        name: cvar_name.to_owned(),
+                    }),
                 }
+            } else {
+                #direct
+            }
+        }
+    };
+
+    // The default is only known when using `cvars!`, with `#[derive(SetGet)]`
+    // it's specified separately by the user.
+    let defaults = values.iter().map(|value| match value {
+        Some(value) => quote! { ::core::option::Option::Some(stringify!(#value)) },
+        None => quote! { ::core::option::Option::None },
+    });
+
+    // Closures which check the cvars' constraints, only for cvars which have some.
+    let validators: Vec<_> = tys
+        .iter()
+        .zip(&opts)
+        .map(|(ty, opts)| validator(ty, opts))
+        .collect();
+    let mins = opts.iter().map(|opts| opt_str(opts.min.as_ref()));
+    let maxs = opts.iter().map(|opts| opt_str(opts.max.as_ref()));
+    let validates = opts.iter().map(|opts| opt_str(opts.validate.as_ref()));
+    let flags: Vec<_> = opts
+        .iter()
+        .map(|opts| {
+            let flags = &opts.flags;
+            quote! { ::cvars::CvarFlags { #( #flags: true, )* ..::cvars::CvarFlags::NONE } }
+        })
+        .collect();
+    let one_ofs = opts.iter().map(|opts| {
+        let allowed = opts.one_of.iter().flatten().map(expr_str);
+        quote! { &[ #( #allowed, )* ] }
+    });
+
+    let mut set_str_arms = Vec::new();
+    for i in 0..names.len() {
+        let name = names[i];
+        let pattern = &patterns[i];
+        let arm = match &validators[i] {
+            Some(validator) => quote! {
+                #pattern => set_str_validated(&mut self.#name, cvar_name, str_value, #validator),
+            },
+            None => quote! {
+                #pattern => set_str(&mut self.#name, cvar_name, str_value),
+            },
+        };
+        set_str_arms.push(arm);
+    }
+    // Only generate the helper if it's used, otherwise it'd cause a dead code warning.
+    // If all the cvars which use it are disabled by `cfg`, it's still unused.
+    let allow_dead_code = if cfgs.iter().any(|cfg| !cfg.is_empty()) {
+        quote! { #[allow(dead_code)] }
+    } else {
+        quote! {}
+    };
+    let set_str_validated = if validators.iter().any(Option::is_some) {
+        quote! {
+            #allow_dead_code
+            #[inline(never)]
+            fn set_str_validated<T>(
+                cvar: &mut T,
+                cvar_name: &str,
+                str_value: &str,
+                validator: impl FnOnce(&T) -> ::core::result::Result<(), String>,
+            ) -> ::core::result::Result<(), ::cvars::CvarError>
+            where
+                T: ::core::str::FromStr,
+                T::Err: ::core::fmt::Display,
+            {
+                let value = parse(cvar_name, str_value)?;
+                if let ::core::result::Result::Err(message) = validator(&value) {
+                    return ::core::result::Result::Err(::cvars::CvarError::Invalid {
+                        name: cvar_name.to_owned(),
+                        value: str_value.to_owned(),
+                        message,
+                    });
+                }
+                *cvar = value;
+                ::core::result::Result::Ok(())
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    // Generic structs need bounds on the cvar types so that the generated code typechecks.
+    // Structs without generics don't get them to keep the amount of generated code small
+    // and the error messages pointing at the offending field.
+    let mut generics = generics.clone();
+    if !generics.params.is_empty() {
+        if options.serde {
+            return Err(syn::Error::new_spanned(
+                &generics.params,
+                "`cvars(serde)` is not supported on generic structs",
+            ));
+        }
+        let unique_tys: HashSet<_> = tys.iter().collect();
+        let where_clause = generics.make_where_clause();
+        for ty in unique_tys {
+            where_clause.predicates.push(parse_quote! {
+                #ty: ::core::any::Any + ::core::str::FromStr + ::core::fmt::Display
+            });
+            where_clause.predicates.push(parse_quote! {
+                <#ty as ::core::str::FromStr>::Err: ::core::fmt::Display
+            });
+        }
+    }
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    // Typed getters and setters only accept types which some cvar has
+    // so that asking for the wrong type is caught at compile time.
+    // This isn't possible if the struct has nested cvars because their types aren't known here
+    // or if cvar types use the struct's generic parameters because the impls could overlap.
+    let generic_params: Vec<_> = generics
+        .type_params()
+        .map(|param| &param.ident)
+        .chain(generics.const_params().map(|param| &param.ident))
+        .collect();
+    let type_checked = nested.is_empty()
+        && !tys
+            .iter()
+            .any(|ty| mentions_any(ty.to_token_stream(), &generic_params));
+    let (type_bound, type_impls) = if type_checked {
+        // Each type gets one impl which exists if any of the cvars with that type exists.
+        let mut unique_tys = Vec::new();
+        let mut tys_cfgs: Vec<Vec<&[Attribute]>> = Vec::new();
+        let mut indices = HashMap::new();
+        for (&ty, opts) in tys.iter().zip(&opts) {
+            let index = *indices.entry(ty).or_insert_with(|| {
+                unique_tys.push(ty);
+                tys_cfgs.push(Vec::new());
+                unique_tys.len() - 1
+            });
+            tys_cfgs[index].push(&opts.cfgs);
+        }
+        let mut impl_cfgs = Vec::new();
+        for ty_cfgs in tys_cfgs {
+            if ty_cfgs.iter().any(|cfgs| cfgs.is_empty()) {
+                impl_cfgs.push(quote! {});
+                continue;
+            }
+            let predicates = ty_cfgs
+                .into_iter()
+                .map(cfg_predicate)
+                .collect::<syn::Result<Vec<_>>>()?;
+            impl_cfgs.push(quote! { #[cfg(any( #( #predicates ),* ))] });
+        }
+        (
+            quote! { __CvarsT: ::cvars::CvarType<Self>, },
+            quote! {
+                #(
+                    #impl_cfgs
+                    #[automatically_derived]
+                    impl #impl_generics ::cvars::CvarType<#struct_name #ty_generics> for #unique_tys #where_clause {}
+                )*
+            },
+        )
+    } else {
+        (quote! {}, quote! {})
+    };
 
-                fn set(cvars: &mut Cvars, cvar_name: &str, value: Self) -> ::core::result::Result<(), String> {
-                    match cvar_name {
-                        #( #setter_arms )*
-                        _ => ::core::result::Result::Err(format!(
-                            "Cvar named {} with type {} not found",
-                            cvar_name,
-                            stringify!(#unique_ty),
-                        )),
+    let set_get_impl = impl_set_get(&struct_name, &generics);
+
+    // Only generate code for notifying observers if the struct has them.
+    // It doesn't cost much but most structs won't use it.
+    let notify_one = |body: proc_macro2::TokenStream| match observers {
+        Some(observers) => quote! {
+            let old_value = if self.#observers.is_observed(cvar_name) {
+                self.get_string(cvar_name).ok()
+            } else {
+                ::core::option::Option::None
+            };
+            let res: ::core::result::Result<(), ::cvars::CvarError> = { #body };
+            if let (::core::result::Result::Ok(()), ::core::option::Option::Some(old_value)) = (&res, old_value) {
+                // The cvar exists, otherwise it would have returned an error.
+                let new_value = self.get_string(cvar_name).unwrap();
+                if new_value != old_value {
+                    self.#observers.notify(cvar_name, &old_value, &new_value);
+                }
+            }
+            res
+        },
+        None => body,
+    };
+    // Nested cvars are reset to the values in the parent's default, not the nested struct's own default,
+    // because the parent can initialize the nested struct differently.
+    let reset_where_body = quote! {
+        #(
+            #cfgs
+            if filter(#flags) {
+                ::core::mem::swap(&mut self.#names, &mut default.#names);
+            }
+        )*
+        #(
+            #nested_cfgs
+            self.#nested.__reset_where_from(filter, &mut default.#nested);
+        )*
+    };
+    let reset_where_body = match observers {
+        Some(observers) => quote! {
+            let old_values: ::core::option::Option<::std::vec::Vec<String>> = if self.#observers.is_empty() {
+                ::core::option::Option::None
+            } else {
+                ::core::option::Option::Some(
+                    self.cvar_infos()
+                        .iter()
+                        .map(|info| self.get_string(info.name).unwrap())
+                        .collect(),
+                )
+            };
+            #reset_where_body
+            if let ::core::option::Option::Some(old_values) = old_values {
+                for (info, old_value) in self.cvar_infos().iter().zip(old_values) {
+                    let new_value = self.get_string(info.name).unwrap();
+                    if new_value != old_value {
+                        self.#observers.notify(info.name, &old_value, &new_value);
                     }
                 }
             }
+        },
+        None => reset_where_body,
+    };
+    let (observers_ref, observers_mut) = match observers {
+        Some(observers) => (
+            quote! { ::core::option::Option::Some(&self.#observers) },
+            quote! { ::core::option::Option::Some(&mut self.#observers) },
+        ),
+        None => (
+            quote! { ::core::option::Option::None },
+            quote! { ::core::option::Option::None },
+        ),
+    };
+
+    // Formatted at runtime because `CvarInfo::one_of` only has the values as written in the source code
+    // which doesn't have to be how they're parsed, e.g. `Split::Vertical` for an enum.
+    let allowed_values = opts.iter().map(|opts| match &opts.one_of {
+        Some(one_of) => quote! {
+            ::std::vec![ #( ::std::string::ToString::to_string(&#one_of), )* ]
+        },
+        None => quote! { ::std::vec::Vec::new() },
+    });
+    let allowed_values_body = route(
+        quote! {
+            match #scrutinee {
+                #( #patterns => ::core::result::Result::Ok(#allowed_values), )*
+                _ => ::core::result::Result::Err(::cvars::CvarError::NotFound {
+                    name: cvar_name.to_owned(),
+                }),
+            }
+        },
+        &|name| quote! { ::cvars::SetGet::allowed_values(&self.#name, rest) },
+    );
+    // Evaluated as the cvar's type first so that e.g. `min = 1` works for `f32` cvars.
+    let ranges = tys.iter().zip(&opts).map(|(ty, opts)| {
+        if opts.min.is_none() && opts.max.is_none() {
+            return quote! { ::core::option::Option::None };
+        }
+        let bound = |bound: Option<&Expr>, unbounded: proc_macro2::TokenStream| match bound {
+            Some(bound) => quote! { ::cvars::__to_f64(&{ let bound: #ty = #bound; bound }) },
+            None => quote! { ::core::option::Option::Some(#unbounded) },
+        };
+        let min = bound(
+            opts.min.as_ref(),
+            quote! { ::core::primitive::f64::NEG_INFINITY },
+        );
+        let max = bound(
+            opts.max.as_ref(),
+            quote! { ::core::primitive::f64::INFINITY },
+        );
+        quote! { #min.zip(#max) }
+    });
+    let cvar_range_body = route(
+        quote! {
+            match #scrutinee {
+                #( #patterns => ::core::result::Result::Ok(#ranges), )*
+                _ => ::core::result::Result::Err(::cvars::CvarError::NotFound {
+                    name: cvar_name.to_owned(),
+                }),
+            }
+        },
+        &|name| quote! { self.#name.__cvar_range(rest) },
+    );
+    let get_string_body = route(
+        quote! {
+            match #scrutinee {
+                #( #patterns => get_string(&self.#names), )*
+                _ => ::core::result::Result::Err(::cvars::CvarError::NotFound {
+                    name: cvar_name.to_owned(),
+                }),
+            }
+        },
+        &|name| quote! { ::cvars::SetGet::get_string(&self.#name, rest) },
+    );
+    let set_str_body = notify_one(route(
+        quote! {
+            match #scrutinee {
+                #( #set_str_arms )*
+                _ => ::core::result::Result::Err(::cvars::CvarError::NotFound {
+                    name: cvar_name.to_owned(),
+                }),
+            }
+        },
+        &|name| quote! { ::cvars::SetGet::set_str(&mut self.#name, rest, str_value) },
+    ));
+    let reset_body = notify_one(route(
+        quote! {
+            // Swap instead of assigning so it also works if the struct impls Drop.
+            match #scrutinee {
+                #( #patterns => ::core::mem::swap(&mut self.#names, &mut default.#names), )*
+                _ => {
+                    return ::core::result::Result::Err(::cvars::CvarError::NotFound {
+                        name: cvar_name.to_owned(),
+                    });
+                }
+            }
+            ::core::result::Result::Ok(())
+        },
+        &|name| quote! { self.#name.__reset_from(rest, &mut default.#name) },
+    ));
+
+    let serde_impls = if options.serde {
+        let serde_names: Vec<_> = names.iter().chain(&nested).copied().collect();
+        let serde_cfgs: Vec<_> = cfgs.iter().chain(&nested_cfgs).collect();
+        // Nested structs check their own cvars when deserializing.
+        let serde_validators: Vec<_> = validators
+            .iter()
+            .cloned()
+            .chain(nested.iter().map(|_| None))
+            .collect();
+        impl_serde(&struct_name, &serde_names, &serde_cfgs, &serde_validators)
+    } else {
+        quote! {}
+    };
+
+    let mut setter_arms = Vec::new();
+    for i in 0..names.len() {
+        let field = names[i];
+        let ty = tys[i];
+        let pattern = &patterns[i];
+
+        let setter_arm = match &validators[i] {
+            Some(validator) => quote! {
+                #pattern => {
+                    let value = downcast::<__CvarsT, #ty>(value, cvar_name)?;
+                    if let ::core::result::Result::Err(message) = (#validator)(&value) {
+                        return ::core::result::Result::Err(::cvars::CvarError::Invalid {
+                            name: cvar_name.to_owned(),
+                            value: value.to_string(),
+                            message,
+                        });
+                    }
+                    self.#field = value;
+                    ::core::result::Result::Ok(())
+                }
+            },
+            None => quote! {
+                #pattern => downcast::<__CvarsT, #ty>(value, cvar_name).map(|value| self.#field = value),
+            },
         };
-        trait_impls.push(trait_impl);
+        setter_arms.push(setter_arm);
     }
+    // All the typed getters go through these so that the per-cvar code is just a cast.
+    let get_any_body = route(
+        quote! {
+            match #scrutinee {
+                #( #patterns => ::core::result::Result::Ok((&self.#names as &dyn ::core::any::Any, ::std::any::type_name::<#tys>())), )*
+                _ => ::core::result::Result::Err(::cvars::CvarError::NotFound {
+                    name: cvar_name.to_owned(),
+                }),
+            }
+        },
+        &|name| quote! { self.#name.__get_any(rest) },
+    );
+    let get_any_mut_body = route(
+        quote! {
+            match #scrutinee {
+                #( #patterns => ::core::result::Result::Ok((&mut self.#names as &mut dyn ::core::any::Any, ::std::any::type_name::<#tys>())), )*
+                _ => ::core::result::Result::Err(::cvars::CvarError::NotFound {
+                    name: cvar_name.to_owned(),
+                }),
+            }
+        },
+        &|name| quote! { self.#name.__get_any_mut(rest) },
+    );
+    let set_body = notify_one(route(
+        quote! {
+            match #scrutinee {
+                #( #setter_arms )*
+                _ => ::core::result::Result::Err(::cvars::CvarError::NotFound {
+                    name: cvar_name.to_owned(),
+                }),
+            }
+        },
+        &|name| quote! { self.#name.__set::<__CvarsT>(rest, value) },
+    ));
 
-    quote! {
+    Ok(quote! {
         #[automatically_derived]
-        impl #struct_name {
+        impl #impl_generics #struct_name #ty_generics #where_clause {
             /// Finds the cvar whose name matches `cvar_name` and returns its value.
             ///
-            /// Returns `Err` if the cvar doesn't exist.
-            pub fn get<T: SetGetType>(&self, cvar_name: &str) -> ::core::result::Result<T, String> {
-                // We can't generate all the match arms here because we don't know what concrete type T is.
-                // Instead, we statically dispatch it through the SetGetType trait and then only look up
-                // fields of the correct type in each impl block.
-                SetGetType::get(self, cvar_name)
+            /// Returns `Err` if the cvar doesn't exist or its type doesn't match the requested type.
+            /// Fails to compile if no cvar has the requested type, see [`CvarType`](::cvars::CvarType).
+            pub fn get<__CvarsT>(&self, cvar_name: &str) -> ::core::result::Result<__CvarsT, ::cvars::CvarError>
+            where
+                __CvarsT: ::core::any::Any + ::core::clone::Clone,
+                #type_bound
+            {
+                self.__get_ref::<__CvarsT>(cvar_name).map(::core::clone::Clone::clone)
+            }
+
+            /// Finds the cvar whose name matches `cvar_name` and returns a reference to its value.
+            ///
+            /// Unlike `get`, this doesn't clone the value.
+            ///
+            /// Returns `Err` if the cvar doesn't exist or its type doesn't match the requested type.
+            /// Fails to compile if no cvar has the requested type, see [`CvarType`](::cvars::CvarType).
+            pub fn get_ref<__CvarsT>(&self, cvar_name: &str) -> ::core::result::Result<&__CvarsT, ::cvars::CvarError>
+            where
+                __CvarsT: ::core::any::Any,
+                #type_bound
+            {
+                self.__get_ref(cvar_name)
+            }
+
+            /// Same as `get_ref` but without the compile time check, used by typed keys which carry the type.
+            #[doc(hidden)]
+            pub fn __get_ref<__CvarsT>(&self, cvar_name: &str) -> ::core::result::Result<&__CvarsT, ::cvars::CvarError>
+            where
+                __CvarsT: ::core::any::Any,
+            {
+                // We don't know what the concrete type is so we look up the field by name
+                // and then check its type at runtime.
+                // The generic parameter has an unusual name so it doesn't clash with those of the struct.
+                let (cvar, ty) = self.__get_any(cvar_name)?;
+                cvar.downcast_ref().ok_or_else(|| ::cvars::CvarError::TypeMismatch {
+                    name: cvar_name.to_owned(),
+                    expected: ty,
+                    found: ::std::any::type_name::<__CvarsT>(),
+                })
+            }
+
+            /// Finds the cvar whose name matches `cvar_name` and returns a mutable reference to its value.
+            ///
+            /// Changing the value this way is the same as changing the field directly,
+            /// constraints are not checked and observers are not called.
+            ///
+            /// Returns `Err` if the cvar doesn't exist or its type doesn't match the requested type.
+            /// Fails to compile if no cvar has the requested type, see [`CvarType`](::cvars::CvarType).
+            pub fn get_mut<__CvarsT>(&mut self, cvar_name: &str) -> ::core::result::Result<&mut __CvarsT, ::cvars::CvarError>
+            where
+                __CvarsT: ::core::any::Any,
+                #type_bound
+            {
+                let (cvar, ty) = self.__get_any_mut(cvar_name)?;
+                cvar.downcast_mut().ok_or_else(|| ::cvars::CvarError::TypeMismatch {
+                    name: cvar_name.to_owned(),
+                    expected: ty,
+                    found: ::std::any::type_name::<__CvarsT>(),
+                })
+            }
+
+            /// Finds the cvar whose name matches `cvar_name` and returns a reference to it as `dyn Any`.
+            ///
+            /// Returns `None` if the cvar doesn't exist.
+            pub fn get_any(&self, cvar_name: &str) -> ::core::option::Option<&dyn ::core::any::Any> {
+                self.__get_any(cvar_name).ok().map(|(cvar, _)| cvar)
+            }
+
+            /// Finds the cvar whose name matches `cvar_name` and returns a mutable reference to it as `dyn Any`.
+            ///
+            /// Changing the value this way is the same as changing the field directly,
+            /// constraints are not checked and observers are not called.
+            ///
+            /// Returns `None` if the cvar doesn't exist.
+            pub fn get_any_mut(&mut self, cvar_name: &str) -> ::core::option::Option<&mut dyn ::core::any::Any> {
+                self.__get_any_mut(cvar_name).ok().map(|(cvar, _)| cvar)
+            }
+
+            /// Returns the cvar as `dyn Any` and the name of its type.
+            #[doc(hidden)]
+            pub fn __get_any(&self, cvar_name: &str) -> ::core::result::Result<(&dyn ::core::any::Any, &'static str), ::cvars::CvarError> {
+                #get_any_body
+            }
+
+            /// Returns the cvar as `dyn Any` and the name of its type.
+            #[doc(hidden)]
+            pub fn __get_any_mut(&mut self, cvar_name: &str) -> ::core::result::Result<(&mut dyn ::core::any::Any, &'static str), ::cvars::CvarError> {
+                #get_any_mut_body
             }
 
             /// Finds the cvar whose name matches `cvar_name` and returns its value as a `String`.
             ///
             /// Returns `Err` if the cvar doesn't exist.
-            pub fn get_string(&self, cvar_name: &str) -> ::core::result::Result<String, String> {
-                // This doesn't need to be dispatched via SetGetType, it uses Display instead.
-
+            pub fn get_string(&self, cvar_name: &str) -> ::core::result::Result<String, ::cvars::CvarError> {
                 // Separate function - see set_str for why.
                 #[inline(never)]
-                fn get_string<T: ::core::fmt::Display>(cvar: &T) -> ::core::result::Result<String, String> {
+                fn get_string<T: ::core::fmt::Display>(cvar: &T) -> ::core::result::Result<String, ::cvars::CvarError> {
                     ::core::result::Result::Ok(cvar.to_string())
                 }
-                match cvar_name {
-                    #( stringify!(#names) => get_string(&self.#names), )*
-                    _ => ::core::result::Result::Err(format!(
-                        "Cvar named {} not found",
-                        cvar_name,
-                    )),
-                }
+                #get_string_body
             }
 
             /// Finds the cvar whose name matches `cvar_name` and sets it to `value`.
@@ -436,26 +1534,70 @@ fn generate(
             /// This in turn means if the cvar's type is e.g. usize and you try
             /// `cvars.set("sometihng_with_type_usize", 123);`, it will fail
             /// because at compile time, `123` is inferred to be `i32`.
-            /// Use `123_usize` to specify the correct type.
+            /// Use `123_usize` to specify the correct type
+            /// or use `set_numeric` which converts between numeric types.
             ///
             /// This limitation doesn't apply to `set_str` since it determines which type to parse to
             /// *after* looking up the right field at runtime.
-            pub fn set<T: SetGetType>(&mut self, cvar_name: &str, value: T) -> ::core::result::Result<(), String> {
-                SetGetType::set(self, cvar_name, value)
+            /// It also doesn't apply to `set_key` since the key carries the cvar's type.
+            ///
+            /// Fails to compile if no cvar has the type of `value`, see [`CvarType`](::cvars::CvarType).
+            pub fn set<__CvarsT>(&mut self, cvar_name: &str, value: __CvarsT) -> ::core::result::Result<(), ::cvars::CvarError>
+            where
+                __CvarsT: ::core::any::Any,
+                #type_bound
+            {
+                self.__set(cvar_name, value)
+            }
+
+            /// Same as `set` but without the compile time check,
+            /// used by typed keys and by parents of nested structs.
+            #[doc(hidden)]
+            pub fn __set<__CvarsT>(&mut self, cvar_name: &str, value: __CvarsT) -> ::core::result::Result<(), ::cvars::CvarError>
+            where
+                __CvarsT: ::core::any::Any,
+            {
+                // Separate function - see set_str for why.
+                #[inline(never)]
+                fn downcast<T, U>(value: T, cvar_name: &str) -> ::core::result::Result<U, ::cvars::CvarError>
+                where
+                    T: ::core::any::Any,
+                    U: ::core::any::Any,
+                {
+                    // Any can only downcast references, wrap the value in an Option so we can move it out.
+                    let mut value = ::core::option::Option::Some(value);
+                    let any: &mut dyn ::core::any::Any = &mut value;
+                    match any.downcast_mut::<::core::option::Option<U>>() {
+                        ::core::option::Option::Some(value) => ::core::result::Result::Ok(value.take().unwrap()),
+                        ::core::option::Option::None => ::core::result::Result::Err(::cvars::CvarError::TypeMismatch {
+                            name: cvar_name.to_owned(),
+                            expected: ::std::any::type_name::<U>(),
+                            found: ::std::any::type_name::<T>(),
+                        }),
+                    }
+                }
+                #set_body
             }
 
             /// Finds the cvar whose name matches `cvar_name`, tries to parse `str_value` to its type and sets it to the parsed value.
             ///
             /// Returns `Err` if the cvar doesn't exist or if `str_value` fails to parse to its type.
-            pub fn set_str(&mut self, cvar_name: &str, str_value: &str) -> ::core::result::Result<(), String> {
-                // This doesn't need to be dispatched via SetGetType, it uses FromStr instead.
-
+            pub fn set_str(&mut self, cvar_name: &str, str_value: &str) -> ::core::result::Result<(), ::cvars::CvarError> {
                 // Put most of the logic in a separate function
                 // so that the repeated code is only a single function call.
                 // This roughly halves incremental compilation time
                 // when the Cvars struct is modified for 1k cvars.
                 #[inline(never)]
-                fn set_str<T>(cvar: &mut T, mut str_value: &str) -> ::core::result::Result<(), String>
+                fn set_str<T>(cvar: &mut T, cvar_name: &str, str_value: &str) -> ::core::result::Result<(), ::cvars::CvarError>
+                where
+                    T: ::core::str::FromStr,
+                    T::Err: ::core::fmt::Display,
+                {
+                    *cvar = parse(cvar_name, str_value)?;
+                    ::core::result::Result::Ok(())
+                }
+                #set_str_validated
+                fn parse<T>(cvar_name: &str, mut str_value: &str) -> ::core::result::Result<T, ::cvars::CvarError>
                 where
                     T: ::core::str::FromStr,
                     T::Err: ::core::fmt::Display,
@@ -467,45 +1609,423 @@ fn generate(
                             str_value = "false";
                         }
                     }
-                    match str_value.parse() {
-                        ::core::result::Result::Ok(val) => ::core::result::Result::Ok(*cvar = val),
-                        ::core::result::Result::Err(err) => ::core::result::Result::Err(format!("failed to parse {} as type {}: {}",
-                            str_value,
-                            ::std::any::type_name::<T>(),
-                            err,
-                        ))
-                    }
-                }
-                match cvar_name {
-                    #( stringify!(#names) => set_str(&mut self.#names, str_value), )*
-                    _ => ::core::result::Result::Err(format!(
-                        "Cvar named {} not found",
-                        cvar_name
-                    )),
+                    str_value.parse().map_err(|err: T::Err| ::cvars::CvarError::Parse {
+                        name: cvar_name.to_owned(),
+                        value: str_value.to_owned(),
+                        ty: ::std::any::type_name::<T>(),
+                        source_message: err.to_string(),
+                    })
                 }
+                #set_str_body
+            }
+
+            /// Returns the value of the cvar identified by `key`.
+            ///
+            /// Returns `Err` only if the key belongs to a different struct.
+            pub fn get_key<__CvarsT>(&self, key: ::cvars::CvarKey<__CvarsT>) -> ::core::result::Result<__CvarsT, ::cvars::CvarError>
+            where
+                __CvarsT: ::core::any::Any + ::core::clone::Clone,
+            {
+                self.__get_ref(key.name()).map(::core::clone::Clone::clone)
+            }
+
+            /// Sets the cvar identified by `key` to `value`.
+            ///
+            /// Unlike with `set`, the type of `value` is inferred from the key.
+            ///
+            /// Returns `Err` if the key belongs to a different struct or the value is rejected by the cvar's constraints.
+            pub fn set_key<__CvarsT>(&mut self, key: ::cvars::CvarKey<__CvarsT>, value: __CvarsT) -> ::core::result::Result<(), ::cvars::CvarError>
+            where
+                __CvarsT: ::core::any::Any,
+            {
+                self.__set(key.name(), value)
+            }
+
+            /// Parses `str_value` and sets the cvar identified by `key` to the parsed value.
+            ///
+            /// Returns `Err` if `str_value` fails to parse or if the key belongs to a different struct.
+            pub fn set_str_key<__CvarsT>(&mut self, key: ::cvars::CvarKey<__CvarsT>, str_value: &str) -> ::core::result::Result<(), ::cvars::CvarError> {
+                self.set_str(key.name(), str_value)
+            }
+
+            /// Finds the cvar whose name matches `cvar_name` and sets it to `value`
+            /// converted to the cvar's numeric type.
+            ///
+            /// Unlike `set`, this accepts any primitive number, e.g. `cvars.set_numeric("cl_fps_max", 60)`
+            /// works for a `usize` cvar and values from scripting languages which only have `f64` work for integer cvars.
+            /// See [`Numeric`](::cvars::Numeric) for which conversions are allowed.
+            ///
+            /// Returns `Err` if the cvar doesn't exist, isn't numeric,
+            /// `value` doesn't fit into its type or is rejected by the cvar's constraints.
+            pub fn set_numeric<__CvarsT>(&mut self, cvar_name: &str, value: __CvarsT) -> ::core::result::Result<(), ::cvars::CvarError>
+            where
+                __CvarsT: ::cvars::Numeric,
+            {
+                ::cvars::__set_numeric(self, cvar_name, value)
+            }
+
+            /// Finds the cvar whose name matches `cvar_name` and returns its value
+            /// converted to the numeric type `__CvarsT`.
+            ///
+            /// See [`Numeric`](::cvars::Numeric) for which conversions are allowed.
+            ///
+            /// Returns `Err` if the cvar doesn't exist, isn't numeric or its value doesn't fit into `__CvarsT`.
+            pub fn get_numeric<__CvarsT>(&self, cvar_name: &str) -> ::core::result::Result<__CvarsT, ::cvars::CvarError>
+            where
+                __CvarsT: ::cvars::Numeric,
+            {
+                ::cvars::__get_numeric(self, cvar_name)
+            }
+
+            /// Finds the cvar whose name matches `cvar_name` and resets it to its default value.
+            ///
+            /// Returns `Err` if the cvar doesn't exist.
+            pub fn reset(&mut self, cvar_name: &str) -> ::core::result::Result<(), ::cvars::CvarError> {
+                let mut default = <Self as ::core::default::Default>::default();
+                self.__reset_from(cvar_name, &mut default)
+            }
+
+            /// Same as `reset` but takes the default values from `default`
+            /// so parents can reset nested cvars to the values in their own default.
+            #[doc(hidden)]
+            pub fn __reset_from(&mut self, cvar_name: &str, default: &mut Self) -> ::core::result::Result<(), ::cvars::CvarError> {
+                #reset_body
+            }
+
+            /// Resets all cvars to their default values.
+            ///
+            /// Fields marked with `#[cvars(skip)]` are not affected.
+            pub fn reset_all(&mut self) {
+                self.reset_where(&|_| true);
+            }
+
+            /// Resets cvars whose flags match `filter` to their default values.
+            ///
+            /// The default values are created only once
+            /// so this is faster than calling `reset` for each cvar.
+            pub fn reset_where(&mut self, filter: &dyn ::core::ops::Fn(::cvars::CvarFlags) -> bool) {
+                let mut default = <Self as ::core::default::Default>::default();
+                self.__reset_where_from(filter, &mut default);
+            }
+
+            /// Same as `reset_where` but takes the default values from `default`, see `__reset_from`.
+            #[doc(hidden)]
+            pub fn __reset_where_from(&mut self, filter: &dyn ::core::ops::Fn(::cvars::CvarFlags) -> bool, default: &mut Self) {
+                #reset_where_body
+            }
+
+            /// Finds the cvar whose name matches `cvar_name` and returns its default value as a `String`.
+            ///
+            /// Returns `Err` if the cvar doesn't exist.
+            pub fn default_string(&self, cvar_name: &str) -> ::core::result::Result<String, ::cvars::CvarError> {
+                <Self as ::core::default::Default>::default().get_string(cvar_name)
+            }
+
+            /// Returns the cvars whose values differ from their defaults, in the same order as in the struct.
+            ///
+            /// The values are compared as strings using their `Display` impls.
+            pub fn changed(&self) -> ::std::vec::Vec<::cvars::ChangedCvar> {
+                let default = <Self as ::core::default::Default>::default();
+                self.cvar_infos()
+                    .iter()
+                    .filter_map(|info| {
+                        // The names come from cvar_infos so they always exist.
+                        let value = self.get_string(info.name).unwrap();
+                        let default = default.get_string(info.name).unwrap();
+                        if value == default {
+                            ::core::option::Option::None
+                        } else {
+                            ::core::option::Option::Some(::cvars::ChangedCvar {
+                                name: info.name,
+                                value,
+                                default,
+                            })
+                        }
+                    })
+                    .collect()
             }
 
-            /// Returns the number of cvars.
+            /// Returns the number of cvars, including those in nested structs.
             pub fn cvar_count(&self) -> usize {
-                #cvar_count
+                Self::CVAR_COUNT
             }
 
-            /// The number of cvars.
+            /// The number of cvars, including those in nested structs.
             pub const CVAR_COUNT: usize = #cvar_count;
+
+            /// Returns information about all cvars such as their names, types and doc comments.
+            ///
+            /// Includes cvars from nested structs, their names are prefixed with the field name and a dot.
+            pub fn cvar_infos(&self) -> &'static [::cvars::CvarInfo] {
+                Self::__cvar_infos()
+            }
+
+            /// Same as `cvar_infos` but doesn't need an instance so nested structs can call it on their children.
+            #[doc(hidden)]
+            pub fn __cvar_infos() -> &'static [::cvars::CvarInfo] {
+                #all_infos
+            }
+
+            #lookup_fn
+
+            /// Finds the cvar whose name matches `cvar_name` and returns information about it.
+            ///
+            /// Returns `None` if the cvar doesn't exist.
+            pub fn cvar_info(&self, cvar_name: &str) -> ::core::option::Option<&'static ::cvars::CvarInfo> {
+                #cvar_info_body
+            }
+
+            /// Finds the cvar whose name matches `cvar_name` and returns its flags.
+            ///
+            /// Returns `None` if the cvar doesn't exist.
+            pub fn cvar_flags(&self, cvar_name: &str) -> ::core::option::Option<::cvars::CvarFlags> {
+                self.cvar_info(cvar_name).map(|info| info.flags)
+            }
+
+            /// Finds the cvar whose name matches `cvar_name` and returns the values
+            /// from `#[cvars(one_of = [...])]` formatted using `Display`.
+            ///
+            /// Returns an empty `Vec` if any value is allowed or `Err` if the cvar doesn't exist.
+            pub fn allowed_values(&self, cvar_name: &str) -> ::core::result::Result<::std::vec::Vec<::std::string::String>, ::cvars::CvarError> {
+                #allowed_values_body
+            }
+
+            /// Finds the cvar whose name matches `cvar_name` and returns the bounds
+            /// from `#[cvars(min = ..., max = ...)]` converted to `f64`.
+            ///
+            /// A missing bound is returned as infinity.
+            /// Returns `None` if the cvar doesn't exist, isn't a primitive number or has no bounds.
+            pub fn cvar_range(&self, cvar_name: &str) -> ::core::option::Option<(f64, f64)> {
+                self.__cvar_range(cvar_name).ok().flatten()
+            }
+
+            #[doc(hidden)]
+            pub fn __cvar_range(&self, cvar_name: &str) -> ::core::result::Result<::core::option::Option<(f64, f64)>, ::cvars::CvarError> {
+                #cvar_range_body
+            }
+
+            /// Returns the observers if the struct has a field marked with `#[cvars(observers)]`.
+            pub fn observers(&self) -> ::core::option::Option<&::cvars::Observers> {
+                #observers_ref
+            }
+
+            /// Returns the observers if the struct has a field marked with `#[cvars(observers)]`.
+            pub fn observers_mut(&mut self) -> ::core::option::Option<&mut ::cvars::Observers> {
+                #observers_mut
+            }
+
+            #(
+                #cfgs
+                #[doc = concat!("Typed key of the `", stringify!(#names), "` cvar, see [`CvarKey`](::cvars::CvarKey).")]
+                pub const #keys: ::cvars::CvarKey<#tys> = ::cvars::CvarKey::new(stringify!(#names));
+            )*
+
+            /// Information about cvars such as their names, types and doc comments.
+            ///
+            /// Only contains cvars defined directly in this struct, use `cvar_infos` to also get nested cvars.
+            pub const CVAR_INFOS: &'static [::cvars::CvarInfo] = &[
+                #(
+                    #cfgs
+                    ::cvars::CvarInfo {
+                        name: stringify!(#names),
+                        ty: stringify!(#tys),
+                        __type_name: ::cvars::TypeNameFn(::std::any::type_name::<#tys>),
+                        default: #defaults,
+                        min: #mins,
+                        max: #maxs,
+                        one_of: #one_ofs,
+                        validate: #validates,
+                        flags: #flags,
+                        doc: #docs,
+                    },
+                )*
+            ];
         }
 
+        #type_impls
+
         #set_get_impl
 
-        /// This trait is needed to dispatch cvar get/set based on its type.
-        /// You're not meant to impl it yourself, it's done automatically
-        /// for all types used as cvars.
-        pub trait SetGetType {
-            fn get(cvars: &Cvars, cvar_name: &str) -> ::core::result::Result<Self, String>
-                where Self: Sized;
-            fn set(cvars: &mut Cvars, cvar_name: &str, value: Self) -> ::core::result::Result<(), String>;
+        #serde_impls
+
+        #name_enum
+    })
+}
+
+/// Combine the predicates of `#[cfg(...)]` attributes into one which holds if they all hold.
+fn cfg_predicate(cfgs: &[Attribute]) -> syn::Result<proc_macro2::TokenStream> {
+    let predicates = cfgs
+        .iter()
+        .map(|cfg| cfg.meta.require_list().map(|list| &list.tokens))
+        .collect::<syn::Result<Vec<_>>>()?;
+    Ok(quote! { all( #( #predicates ),* ) })
+}
+
+/// Whether `tokens` contain any of `idents`, e.g. whether a type uses the struct's generic parameters.
+fn mentions_any(tokens: proc_macro2::TokenStream, idents: &[&Ident]) -> bool {
+    tokens.into_iter().any(|token| match token {
+        TokenTree::Ident(ident) => idents.contains(&&ident),
+        TokenTree::Group(group) => mentions_any(group.stream(), idents),
+        _ => false,
+    })
+}
+
+/// Generate a function which finds `names` by binary search in a sorted table
+/// and returns the corresponding item from `results`.
+fn lookup_table_fn(
+    fn_name: &Ident,
+    result_ty: &proc_macro2::TokenStream,
+    names: &[&Ident],
+    results: &[proc_macro2::TokenStream],
+    cfgs: &[proc_macro2::TokenStream],
+) -> proc_macro2::TokenStream {
+    let mut sorted: Vec<_> = names.iter().zip(results).zip(cfgs).collect();
+    sorted.sort_by_key(|((name, _), _)| name.to_string());
+    let table_names = sorted.iter().map(|((name, _), _)| name);
+    let table_results = sorted.iter().map(|((_, result), _)| result);
+    let table_cfgs = sorted.iter().map(|(_, cfg)| cfg);
+    quote! {
+        /// Finds the cvar by binary search in a table sorted by name.
+        #[inline(never)]
+        fn #fn_name(cvar_name: &str) -> ::core::option::Option<#result_ty> {
+            // A slice instead of an array because `cfg` can remove elements.
+            static TABLE: &[(&str, #result_ty)] = &[
+                #( #table_cfgs (stringify!(#table_names), #table_results), )*
+            ];
+            TABLE
+                .binary_search_by(|(name, _)| (*name).cmp(cvar_name))
+                .ok()
+                .map(|pos| TABLE[pos].1)
+        }
+    }
+}
+
+/// Convert cvar names to enum variants, e.g. `g_rocket_damage` to `GRocketDamage`.
+fn enum_variants(names: &[&Ident], cfgs: &[proc_macro2::TokenStream]) -> syn::Result<Vec<Ident>> {
+    let mut variants: Vec<Ident> = Vec::new();
+    for (&name, cfg) in names.iter().zip(cfgs) {
+        let mut variant = String::new();
+        for part in name.to_string().trim_start_matches("r#").split('_') {
+            let mut chars = part.chars();
+            if let Some(first) = chars.next() {
+                variant.extend(first.to_uppercase());
+                variant.extend(chars);
+            }
+        }
+        if variant.is_empty() || variant.starts_with(|c: char| c.is_ascii_digit()) {
+            return Err(syn::Error::new_spanned(
+                name,
+                format!("cvar `{name}` can't be converted to an enum variant"),
+            ));
+        }
+        let variant = format_ident!("{}", variant, span = name.span());
+        if cfg.is_empty() && variants.contains(&variant) {
+            return Err(syn::Error::new_spanned(
+                name,
+                format!(
+                    "the enum variant of cvar `{name}` would clash with another one: `{variant}`"
+                ),
+            ));
+        }
+        variants.push(variant);
+    }
+    Ok(variants)
+}
+
+/// Generate the enum with one variant per cvar and its impls.
+fn impl_name_enum(
+    struct_name: &Ident,
+    enum_name: &Ident,
+    vis: &Visibility,
+    names: &[&Ident],
+    variants: &[Ident],
+    cfgs: &[proc_macro2::TokenStream],
+    lookup_table: bool,
+) -> proc_macro2::TokenStream {
+    let from_name = if lookup_table {
+        // Statics can't refer to `Self`.
+        let results: Vec<_> = variants
+            .iter()
+            .map(|variant| quote! { #enum_name::#variant })
+            .collect();
+        lookup_table_fn(
+            &format_ident!("__from_name"),
+            &quote! { #enum_name },
+            names,
+            &results,
+            cfgs,
+        )
+    } else {
+        quote! {
+            /// Finds the cvar by matching on its name.
+            fn __from_name(cvar_name: &str) -> ::core::option::Option<Self> {
+                match cvar_name {
+                    #(
+                        #cfgs
+                        stringify!(#names) => ::core::option::Option::Some(Self::#variants),
+                    )*
+                    _ => ::core::option::Option::None,
+                }
+            }
+        }
+    };
+    let docs = names.iter().map(|name| format!("The `{name}` cvar."));
+
+    quote! {
+        #[doc = concat!("Names of the cvars in `", stringify!(#struct_name), "`, generated by `#[cvars(name_enum)]`.")]
+        #[derive(
+            ::core::fmt::Debug,
+            ::core::clone::Clone,
+            ::core::marker::Copy,
+            ::core::cmp::PartialEq,
+            ::core::cmp::Eq,
+            ::core::cmp::PartialOrd,
+            ::core::cmp::Ord,
+            ::core::hash::Hash,
+        )]
+        #vis enum #enum_name {
+            #(
+                #cfgs
+                #[doc = #docs]
+                #variants,
+            )*
+        }
+
+        #[automatically_derived]
+        impl #enum_name {
+            /// All the cvars in the order in which they're defined.
+            pub const ALL: &'static [#enum_name] = &[ #( #cfgs Self::#variants, )* ];
+
+            /// The cvar's name.
+            pub fn as_str(self) -> &'static str {
+                match self {
+                    #(
+                        #cfgs
+                        Self::#variants => stringify!(#names),
+                    )*
+                }
+            }
+
+            #from_name
+        }
+
+        #[automatically_derived]
+        impl ::core::str::FromStr for #enum_name {
+            type Err = ::cvars::CvarError;
+
+            fn from_str(cvar_name: &str) -> ::core::result::Result<Self, Self::Err> {
+                Self::__from_name(cvar_name).ok_or_else(|| ::cvars::CvarError::NotFound {
+                    name: cvar_name.to_owned(),
+                })
+            }
         }
 
-        #( #trait_impls )*
+        #[automatically_derived]
+        impl ::core::fmt::Display for #enum_name {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                f.write_str(self.as_str())
+            }
+        }
     }
 }
 
@@ -523,26 +2043,69 @@ pub fn derive_dummy(input: TokenStream) -> TokenStream {
 
     let input: DeriveInput = parse_macro_input!(input);
     let struct_name = input.ident;
-    let set_get_impl = impl_set_get(&struct_name);
+    let set_get_impl = impl_set_get(&struct_name, &input.generics);
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
 
     let expanded = quote! {
         #[automatically_derived]
-        impl #struct_name {
-            pub fn get<T>(&self, cvar_name: &str) -> ::core::result::Result<T, String> {
+        impl #impl_generics #struct_name #ty_generics #where_clause {
+            pub fn get<T>(&self, cvar_name: &str) -> ::core::result::Result<T, ::cvars::CvarError> {
+                unimplemented!("SetGetDummy is only for compile time testing.");
+            }
+            pub fn get_string(&self, cvar_name: &str) -> ::core::result::Result<String, ::cvars::CvarError> {
+                unimplemented!("SetGetDummy is only for compile time testing.");
+            }
+            pub fn get_any(&self, cvar_name: &str) -> ::core::option::Option<&dyn ::core::any::Any> {
+                unimplemented!("SetGetDummy is only for compile time testing.");
+            }
+            pub fn get_any_mut(&mut self, cvar_name: &str) -> ::core::option::Option<&mut dyn ::core::any::Any> {
+                unimplemented!("SetGetDummy is only for compile time testing.");
+            }
+            pub fn set<T>(&mut self, cvar_name: &str, value: T) -> ::core::result::Result<(), ::cvars::CvarError> {
+                unimplemented!("SetGetDummy is only for compile time testing.");
+            }
+            pub fn set_str(&mut self, cvar_name: &str, str_value: &str) -> ::core::result::Result<(), ::cvars::CvarError> {
+                unimplemented!("SetGetDummy is only for compile time testing.");
+            }
+            pub fn reset(&mut self, cvar_name: &str) -> ::core::result::Result<(), ::cvars::CvarError> {
+                unimplemented!("SetGetDummy is only for compile time testing.");
+            }
+            pub fn reset_all(&mut self) {
                 unimplemented!("SetGetDummy is only for compile time testing.");
             }
-            pub fn get_string(&self, cvar_name: &str) -> ::core::result::Result<String, String> {
+            pub fn reset_where(&mut self, filter: &dyn ::core::ops::Fn(::cvars::CvarFlags) -> bool) {
                 unimplemented!("SetGetDummy is only for compile time testing.");
             }
-            pub fn set<T>(&mut self, cvar_name: &str, value: T) -> ::core::result::Result<(), String> {
+            pub fn default_string(&self, cvar_name: &str) -> ::core::result::Result<String, ::cvars::CvarError> {
                 unimplemented!("SetGetDummy is only for compile time testing.");
             }
-            pub fn set_str(&mut self, cvar_name: &str, str_value: &str) -> ::core::result::Result<(), String> {
+            pub fn changed(&self) -> ::std::vec::Vec<::cvars::ChangedCvar> {
                 unimplemented!("SetGetDummy is only for compile time testing.");
             }
             pub fn cvar_count(&self) -> usize {
                 unimplemented!("SetGetDummy is only for compile time testing.");
             }
+            pub fn cvar_infos(&self) -> &'static [::cvars::CvarInfo] {
+                unimplemented!("SetGetDummy is only for compile time testing.");
+            }
+            pub fn cvar_info(&self, cvar_name: &str) -> ::core::option::Option<&'static ::cvars::CvarInfo> {
+                unimplemented!("SetGetDummy is only for compile time testing.");
+            }
+            pub fn cvar_flags(&self, cvar_name: &str) -> ::core::option::Option<::cvars::CvarFlags> {
+                unimplemented!("SetGetDummy is only for compile time testing.");
+            }
+            pub fn allowed_values(&self, cvar_name: &str) -> ::core::result::Result<::std::vec::Vec<::std::string::String>, ::cvars::CvarError> {
+                unimplemented!("SetGetDummy is only for compile time testing.");
+            }
+            pub fn cvar_range(&self, cvar_name: &str) -> ::core::option::Option<(f64, f64)> {
+                unimplemented!("SetGetDummy is only for compile time testing.");
+            }
+            pub fn observers(&self) -> ::core::option::Option<&::cvars::Observers> {
+                unimplemented!("SetGetDummy is only for compile time testing.");
+            }
+            pub fn observers_mut(&mut self) -> ::core::option::Option<&mut ::cvars::Observers> {
+                unimplemented!("SetGetDummy is only for compile time testing.");
+            }
         }
 
         #set_get_impl
@@ -557,21 +2120,254 @@ pub fn derive_dummy(input: TokenStream) -> TokenStream {
     expanded
 }
 
-fn impl_set_get(struct_name: &Ident) -> proc_macro2::TokenStream {
+/// Generate a closure which checks a new value against the constraints
+/// from `#[cvars(min = ..., max = ..., one_of = [...], validate = ...)]`.
+///
+/// Returns `None` if the cvar has no constraints.
+fn validator(ty: &Type, opts: &FieldOptions) -> Option<proc_macro2::TokenStream> {
+    if opts.min.is_none() && opts.max.is_none() && opts.one_of.is_none() && opts.validate.is_none()
+    {
+        return None;
+    }
+
+    let min_check = opts.min.as_ref().map(|min| {
+        let min_str = expr_str(min);
+        quote! {
+            if *value < #min {
+                return ::core::result::Result::Err(format!("must be at least {}", #min_str));
+            }
+        }
+    });
+    let max_check = opts.max.as_ref().map(|max| {
+        let max_str = expr_str(max);
+        quote! {
+            if *value > #max {
+                return ::core::result::Result::Err(format!("must be at most {}", #max_str));
+            }
+        }
+    });
+    let one_of_check = opts.one_of.as_ref().map(|one_of| {
+        let allowed_strs = one_of.iter().map(expr_str);
+        quote! {
+            if ![ #( #one_of, )* ].iter().any(|allowed| value == allowed) {
+                let allowed_strs: &[&str] = &[ #( #allowed_strs, )* ];
+                return ::core::result::Result::Err(format!("must be one of: {}", allowed_strs.join(", ")));
+            }
+        }
+    });
+    // The function returns `Result<(), String>` with a message explaining why the value is invalid.
+    let validate_check = opts.validate.as_ref().map(|validate| {
+        quote! {
+            (#validate)(value)?;
+        }
+    });
+
+    Some(quote! {
+        |value: &#ty| -> ::core::result::Result<(), String> {
+            #min_check
+            #max_check
+            #one_of_check
+            #validate_check
+            ::core::result::Result::Ok(())
+        }
+    })
+}
+
+/// Generate `Some(stringified expr)` or `None`.
+fn opt_str(expr: Option<&Expr>) -> proc_macro2::TokenStream {
+    match expr {
+        Some(expr) => {
+            let s = expr_str(expr);
+            quote! { ::core::option::Option::Some(#s) }
+        }
+        None => quote! { ::core::option::Option::None },
+    }
+}
+
+/// Generate a string literal containing the expression as written in the source code.
+///
+/// String literals are used as is so they don't end up quoted twice.
+fn expr_str(expr: &Expr) -> proc_macro2::TokenStream {
+    match expr {
+        Expr::Lit(ExprLit {
+            lit: Lit::Str(lit), ..
+        }) => quote! { #lit },
+        // stringify! would put a space after the minus sign.
+        Expr::Unary(ExprUnary {
+            op: UnOp::Neg(_),
+            expr: inner,
+            ..
+        }) if matches!(**inner, Expr::Lit(_)) => quote! { concat!("-", stringify!(#inner)) },
+        _ => quote! { stringify!(#expr) },
+    }
+}
+
+fn impl_set_get(struct_name: &Ident, generics: &Generics) -> proc_macro2::TokenStream {
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
     quote! {
         #[automatically_derived]
-        impl ::cvars::SetGet for #struct_name {
-            fn get_string(&self, cvar_name: &str) -> ::core::result::Result<String, String> {
+        impl #impl_generics ::cvars::SetGet for #struct_name #ty_generics #where_clause {
+            fn get_string(&self, cvar_name: &str) -> ::core::result::Result<String, ::cvars::CvarError> {
                 self.get_string(cvar_name)
             }
 
-            fn set_str(&mut self, cvar_name: &str, cvar_value: &str) -> ::core::result::Result<(), String> {
+            fn set_str(&mut self, cvar_name: &str, cvar_value: &str) -> ::core::result::Result<(), ::cvars::CvarError> {
                 self.set_str(cvar_name, cvar_value)
             }
 
+            fn get_any(&self, cvar_name: &str) -> ::core::option::Option<&dyn ::core::any::Any> {
+                self.get_any(cvar_name)
+            }
+
+            fn get_any_mut(&mut self, cvar_name: &str) -> ::core::option::Option<&mut dyn ::core::any::Any> {
+                self.get_any_mut(cvar_name)
+            }
+
+            fn reset(&mut self, cvar_name: &str) -> ::core::result::Result<(), ::cvars::CvarError> {
+                self.reset(cvar_name)
+            }
+
+            fn reset_all(&mut self) {
+                self.reset_all()
+            }
+
+            fn reset_where(&mut self, filter: &dyn ::core::ops::Fn(::cvars::CvarFlags) -> bool) {
+                self.reset_where(filter)
+            }
+
+            fn default_string(&self, cvar_name: &str) -> ::core::result::Result<String, ::cvars::CvarError> {
+                self.default_string(cvar_name)
+            }
+
+            fn changed(&self) -> ::std::vec::Vec<::cvars::ChangedCvar> {
+                self.changed()
+            }
+
             fn cvar_count(&self) -> usize {
                 self.cvar_count()
             }
+
+            fn cvar_infos(&self) -> &'static [::cvars::CvarInfo] {
+                self.cvar_infos()
+            }
+
+            fn cvar_info(&self, cvar_name: &str) -> ::core::option::Option<&'static ::cvars::CvarInfo> {
+                self.cvar_info(cvar_name)
+            }
+
+            fn cvar_flags(&self, cvar_name: &str) -> ::core::option::Option<::cvars::CvarFlags> {
+                self.cvar_flags(cvar_name)
+            }
+
+            fn allowed_values(&self, cvar_name: &str) -> ::core::result::Result<::std::vec::Vec<::std::string::String>, ::cvars::CvarError> {
+                self.allowed_values(cvar_name)
+            }
+
+            fn cvar_range(&self, cvar_name: &str) -> ::core::option::Option<(f64, f64)> {
+                self.cvar_range(cvar_name)
+            }
+
+            fn observers(&self) -> ::core::option::Option<&::cvars::Observers> {
+                self.observers()
+            }
+
+            fn observers_mut(&mut self) -> ::core::option::Option<&mut ::cvars::Observers> {
+                self.observers_mut()
+            }
+        }
+    }
+}
+
+fn impl_serde(
+    struct_name: &Ident,
+    names: &[&Ident],
+    cfgs: &[&proc_macro2::TokenStream],
+    validators: &[Option<proc_macro2::TokenStream>],
+) -> proc_macro2::TokenStream {
+    // Deserializing has to check the same constraints as setting the cvar, otherwise a config file
+    // could contain values which can't be set from the console.
+    let deserialize_arms = names.iter().zip(validators).map(|(name, validator)| match validator {
+        Some(validator) => quote! {
+            stringify!(#name) => {
+                let value = map.next_value()?;
+                if let ::core::result::Result::Err(message) = (#validator)(&value) {
+                    let err = ::cvars::CvarError::Invalid {
+                        name: stringify!(#name).to_owned(),
+                        value: value.to_string(),
+                        message,
+                    };
+                    return ::core::result::Result::Err(::cvars::__serde::de::Error::custom(err));
+                }
+                cvars.#name = value;
+            }
+        },
+        None => quote! {
+            stringify!(#name) => cvars.#name = map.next_value()?,
+        },
+    });
+
+    quote! {
+        #[automatically_derived]
+        impl ::cvars::__serde::Serialize for #struct_name {
+            fn serialize<S>(&self, serializer: S) -> ::core::result::Result<S::Ok, S::Error>
+            where
+                S: ::cvars::__serde::Serializer,
+            {
+                use ::cvars::__serde::ser::SerializeStruct;
+
+                const FIELDS: &[&str] = &[ #( #cfgs stringify!(#names), )* ];
+
+                let mut state = serializer.serialize_struct(stringify!(#struct_name), FIELDS.len())?;
+                #(
+                    #cfgs
+                    state.serialize_field(stringify!(#names), &self.#names)?;
+                )*
+                state.end()
+            }
+        }
+
+        #[automatically_derived]
+        impl<'de> ::cvars::__serde::Deserialize<'de> for #struct_name {
+            fn deserialize<D>(deserializer: D) -> ::core::result::Result<Self, D::Error>
+            where
+                D: ::cvars::__serde::Deserializer<'de>,
+            {
+                const FIELDS: &[&str] = &[ #( #cfgs stringify!(#names), )* ];
+
+                struct Visitor;
+
+                impl<'de> ::cvars::__serde::de::Visitor<'de> for Visitor {
+                    type Value = #struct_name;
+
+                    fn expecting(&self, formatter: &mut ::core::fmt::Formatter) -> ::core::fmt::Result {
+                        formatter.write_str(concat!("struct ", stringify!(#struct_name)))
+                    }
+
+                    fn visit_map<A>(self, mut map: A) -> ::core::result::Result<Self::Value, A::Error>
+                    where
+                        A: ::cvars::__serde::de::MapAccess<'de>,
+                    {
+                        // Cvars missing from the input keep their default values.
+                        let mut cvars = <#struct_name as ::core::default::Default>::default();
+                        while let ::core::option::Option::Some(key) = map.next_key::<::std::string::String>()? {
+                            match key.as_str() {
+                                #(
+                                    #cfgs
+                                    #deserialize_arms
+                                )*
+                                _ => {
+                                    return ::core::result::Result::Err(
+                                        ::cvars::__serde::de::Error::unknown_field(&key, FIELDS),
+                                    );
+                                }
+                            }
+                        }
+                        ::core::result::Result::Ok(cvars)
+                    }
+                }
+
+                deserializer.deserialize_struct(stringify!(#struct_name), FIELDS, Visitor)
+            }
         }
     }
 }
@@ -11,6 +11,28 @@ fn tests() {
     t.compile_fail("tests/test_fnlike_sorted_bad.rs");
     t.pass("tests/test_derive_sorted_good.rs");
     t.compile_fail("tests/test_derive_sorted_bad.rs");
+    t.pass("tests/test_fnlike_defaults.rs");
+    t.pass("tests/test_derive_defaults.rs");
+    t.pass("tests/test_fnlike_constraints.rs");
+    t.pass("tests/test_derive_constraints.rs");
+    t.pass("tests/test_fnlike_nested.rs");
+    t.pass("tests/test_fnlike_nested_default.rs");
+    t.pass("tests/test_derive_nested.rs");
+    t.pass("tests/test_fnlike_names.rs");
+    t.pass("tests/test_derive_names.rs");
+    t.pass("tests/test_derive_generics.rs");
+    t.pass("tests/test_fnlike_lookup_table.rs");
+    t.pass("tests/test_derive_lookup_table.rs");
+    t.pass("tests/test_fnlike_name_enum.rs");
+    t.pass("tests/test_derive_name_enum.rs");
+    t.pass("tests/test_fnlike_cfg.rs");
+    t.compile_fail("tests/test_derive_bad_attrs.rs");
+    t.compile_fail("tests/test_fnlike_bad_attrs.rs");
+    t.compile_fail("tests/test_derive_unsupported.rs");
+    t.compile_fail("tests/test_fnlike_duplicate.rs");
+    t.compile_fail("tests/test_fnlike_all_skipped.rs");
+    t.compile_fail("tests/test_fnlike_key_clash.rs");
+    t.compile_fail("tests/test_fnlike_wrong_type.rs");
 
     // Run all test_* files for both derive and fnlike macros.
     // The files need to be copied to different directories because of how trybuild works.
@@ -16,6 +16,10 @@ fn main() {
     g_enum: Two,
     g_string: "String",
     g_skipped: 666,
+    observers: Observers {
+        global: 0,
+        by_name: [],
+    },
 }"#;
     assert_eq!(format!("{:#?}", cvars2), expected);
 }
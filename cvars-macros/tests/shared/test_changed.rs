@@ -0,0 +1,34 @@
+mod shared;
+
+use cvars::{ChangedCvar, SetGet};
+
+use shared::{Cvars, Enum};
+
+fn main() {
+    let mut cvars = Cvars::default();
+    assert_eq!(cvars.changed(), []);
+
+    cvars.g_int = 43;
+    cvars.g_enum = Enum::One;
+    cvars.g_skipped = 667;
+    // Same as the default
+    cvars.g_float = 5.0;
+
+    let expected = [
+        ChangedCvar {
+            name: "g_int",
+            value: "43".to_owned(),
+            default: "42".to_owned(),
+        },
+        ChangedCvar {
+            name: "g_enum",
+            value: "One".to_owned(),
+            default: "Two".to_owned(),
+        },
+    ];
+    assert_eq!(cvars.changed(), expected);
+
+    // Also through the trait
+    let dyn_cvars: &dyn SetGet = &cvars;
+    assert_eq!(dyn_cvars.changed(), expected);
+}
@@ -0,0 +1,127 @@
+mod shared;
+
+use cvars::{
+    config::{self, ConfigError, LoadOptions, SaveOptions},
+    CvarError,
+};
+
+use shared::{Cvars, Enum};
+
+fn main() {
+    let mut cvars = Cvars::default();
+    cvars.g_string = "with spaces and \"quotes\" // not a comment".to_owned();
+
+    let text = config::save(&cvars, SaveOptions::default());
+    let expected = r#"g_bool true
+g_int 42
+g_usize 987654
+g_float 5
+g_double 10
+g_enum Two
+g_string "with spaces and \"quotes\" // not a comment"
+"#;
+    assert_eq!(text, expected);
+
+    let mut loaded = Cvars::default();
+    config::load(&mut loaded, &text, LoadOptions::default()).unwrap();
+    assert_eq!(loaded.g_string, cvars.g_string);
+    assert_eq!(config::save(&loaded, SaveOptions::default()), text);
+
+    let options = SaveOptions {
+        only_changed: true,
+        ..SaveOptions::default()
+    };
+    let text = config::save(&cvars, options);
+    let expected = r#"g_string "with spaces and \"quotes\" // not a comment"
+"#;
+    assert_eq!(text, expected);
+
+    let options = SaveOptions {
+        only_archive: true,
+        ..SaveOptions::default()
+    };
+    assert_eq!(config::save(&cvars, options), "g_int 42\n");
+    let options = SaveOptions {
+        only_changed: true,
+        only_archive: true,
+    };
+    assert_eq!(config::save(&cvars, options), "");
+
+    // Newlines and tabs are escaped so the value stays on one line.
+    cvars.g_string = "two\nlines\tand a tab".to_owned();
+    let options = SaveOptions {
+        only_changed: true,
+        ..SaveOptions::default()
+    };
+    let text = config::save(&cvars, options);
+    assert_eq!(text, "g_string \"two\\nlines\\tand a tab\"\n");
+    let mut loaded = Cvars::default();
+    config::load(&mut loaded, &text, LoadOptions::default()).unwrap();
+    assert_eq!(loaded.g_string, cvars.g_string);
+
+    // Values with characters which are special in the console are quoted too.
+    cvars.g_string = "it's;a".to_owned();
+    let text = config::save(&cvars, options);
+    assert_eq!(text, "g_string \"it's;a\"\n");
+    let mut loaded = Cvars::default();
+    config::load(&mut loaded, &text, LoadOptions::default()).unwrap();
+    assert_eq!(loaded.g_string, cvars.g_string);
+
+    // Same syntax as the console.
+    let text = r#"
+        // Comment
+        g_int 43 // Trailing comment
+        g_usize 5// Comment right after the value
+
+        g_enum one; g_bool false
+        g_string ""
+        g_double 'single quoted'
+    "#;
+    let errors = config::load(&mut loaded, text, LoadOptions::default()).unwrap_err();
+    assert_eq!(loaded.g_int, 43);
+    assert_eq!(loaded.g_usize, 5);
+    assert_eq!(loaded.g_enum, Enum::One);
+    assert!(!loaded.g_bool);
+    assert_eq!(loaded.g_string, "");
+    assert_eq!(
+        errors[0].to_string(),
+        "line 8: failed to parse single quoted as type f64: invalid float literal"
+    );
+
+    // Invalid lines are reported but don't prevent the rest from being applied.
+    let text = r#"g_int 44
+g_int
+bla 5
+g_bool "unterminated
+g_usize not_a_num
+g_float 7.0
+"#;
+    let mut loaded = Cvars::default();
+    let errors = config::load(&mut loaded, text, LoadOptions::default()).unwrap_err();
+    let messages: Vec<_> = errors.iter().map(|err| err.to_string()).collect();
+    assert_eq!(
+        messages,
+        [
+            "line 2: missing value for g_int",
+            "line 3: Cvar named bla not found",
+            "line 4: column 8: unterminated double quote",
+            "line 5: failed to parse not_a_num as type usize: invalid digit found in string",
+        ]
+    );
+    assert!(matches!(
+        &errors[1],
+        ConfigError::Cvar {
+            line: 3,
+            error: CvarError::NotFound { .. },
+        }
+    ));
+    assert_eq!(loaded.g_int, 44);
+    assert_eq!(loaded.g_float, 7.0);
+
+    // Strict mode stops at the first error.
+    let mut loaded = Cvars::default();
+    let errors = config::load(&mut loaded, text, LoadOptions { strict: true }).unwrap_err();
+    assert_eq!(errors.len(), 1);
+    assert_eq!(loaded.g_int, 44);
+    assert_eq!(loaded.g_float, 5.0);
+}
@@ -0,0 +1,33 @@
+mod shared;
+
+use cvars::SetGet;
+
+use shared::Cvars;
+
+fn main() {
+    let cvars = Cvars::default();
+
+    let names: Vec<_> = cvars.cvar_infos().iter().map(|info| info.name).collect();
+    assert_eq!(
+        names,
+        ["g_bool", "g_int", "g_usize", "g_float", "g_double", "g_enum", "g_string"]
+    );
+    assert_eq!(Cvars::CVAR_INFOS.len(), Cvars::CVAR_COUNT);
+
+    let info = cvars.cvar_info("g_double").unwrap();
+    assert_eq!(info.name, "g_double");
+    assert_eq!(info.ty, "f64");
+    assert_eq!(info.doc, "Doc comment - passed to macros as an attribute");
+
+    let info = cvars.cvar_info("g_enum").unwrap();
+    assert_eq!(info.ty, "Enum");
+    assert_eq!(info.doc, "");
+
+    assert_eq!(cvars.cvar_info("g_skipped"), None);
+    assert_eq!(cvars.cvar_info("bla"), None);
+
+    // Also through the trait
+    let cvars: &dyn SetGet = &cvars;
+    assert_eq!(cvars.cvar_infos().len(), 7);
+    assert_eq!(cvars.cvar_info("g_int").unwrap().ty, "i32");
+}
@@ -1,6 +1,6 @@
 mod shared;
 
-use cvars::SetGet;
+use cvars::{CvarError, SetGet};
 
 use shared::Cvars;
 
@@ -20,11 +20,18 @@ fn take_dyn(cvars: &mut dyn SetGet) {
 
     assert_eq!(
         cvars.set_str("bla", "666"),
-        Err("Cvar named bla not found".to_owned())
+        Err(CvarError::NotFound {
+            name: "bla".to_owned()
+        })
     );
     assert_eq!(
         cvars.set_str("g_int", "not a num"),
-        Err("failed to parse not a num as type i32: invalid digit found in string".to_owned())
+        Err(CvarError::Parse {
+            name: "g_int".to_owned(),
+            value: "not a num".to_owned(),
+            ty: "i32",
+            source_message: "invalid digit found in string".to_owned(),
+        })
     );
 
     assert_eq!(cvars.get_string("g_bool"), Ok("false".to_owned()));
@@ -37,6 +44,8 @@ fn take_dyn(cvars: &mut dyn SetGet) {
 
     assert_eq!(
         cvars.get_string("bla"),
-        Err("Cvar named bla not found".to_owned())
+        Err(CvarError::NotFound {
+            name: "bla".to_owned()
+        })
     );
 }
@@ -0,0 +1,46 @@
+mod shared;
+
+use cvars::{CvarFlags, SetGet};
+
+use shared::Cvars;
+
+fn main() {
+    let mut cvars = Cvars::default();
+
+    assert_eq!(cvars.cvar_flags("g_bool"), Some(CvarFlags::NONE));
+    assert_eq!(
+        cvars.cvar_flags("g_int"),
+        Some(CvarFlags {
+            archive: true,
+            ..CvarFlags::default()
+        })
+    );
+    assert_eq!(
+        cvars.cvar_info("g_string").unwrap().flags,
+        CvarFlags {
+            cheat: true,
+            read_only: true,
+            archive: false,
+            replicated: true,
+            user_info: true,
+        }
+    );
+    assert_eq!(cvars.cvar_flags("g_skipped"), None);
+    assert_eq!(cvars.cvar_flags("bla"), None);
+
+    // Flags are only metadata, they're enforced by the code using them (e.g. consoles).
+    cvars.set_str("g_string", "changed").unwrap();
+
+    // Resetting by flags
+    cvars.g_int = 43;
+    cvars.g_float = 1.0;
+    cvars.reset_where(&|flags| flags.archive);
+    assert_eq!(cvars.g_int, 42);
+    assert_eq!(cvars.g_float, 1.0);
+    assert_eq!(cvars.g_string, "changed");
+
+    // Also through the trait
+    let dyn_cvars: &dyn SetGet = &cvars;
+    assert!(dyn_cvars.cvar_flags("g_string").unwrap().cheat);
+    assert!(!dyn_cvars.cvar_flags("g_float").unwrap().cheat);
+}
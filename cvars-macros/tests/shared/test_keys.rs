@@ -0,0 +1,40 @@
+mod shared;
+
+use std::collections::HashSet;
+
+use cvars::{CvarError, CvarKey};
+
+use shared::{Cvars, Enum};
+
+fn main() {
+    let mut cvars = Cvars::default();
+
+    assert_eq!(cvars.get_key(Cvars::G_INT).unwrap(), 42);
+    assert_eq!(cvars.get_key(Cvars::G_STRING).unwrap(), "String");
+    assert_eq!(cvars.get_key(Cvars::G_ENUM).unwrap(), Enum::Two);
+
+    // The type is inferred from the key, no need for `5_usize`.
+    cvars.set_key(Cvars::G_USIZE, 5).unwrap();
+    assert_eq!(cvars.g_usize, 5);
+    cvars.set_key(Cvars::G_DOUBLE, 1.5).unwrap();
+    assert_eq!(cvars.g_double, 1.5);
+    cvars.set_str_key(Cvars::G_ENUM, "one").unwrap();
+    assert_eq!(cvars.g_enum, Enum::One);
+    assert!(matches!(
+        cvars.set_str_key(Cvars::G_BOOL, "bla"),
+        Err(CvarError::Parse { .. })
+    ));
+
+    let key: CvarKey<f32> = Cvars::G_FLOAT;
+    assert_eq!(key.name(), "g_float");
+    assert_eq!(format!("{key:?}"), "CvarKey(\"g_float\")");
+    let keys: HashSet<_> = [Cvars::G_FLOAT, key].into_iter().collect();
+    assert_eq!(keys.len(), 1);
+
+    // Keys are just names, a key with the wrong name or type is only detected at runtime.
+    let foreign: CvarKey<i32> = CvarKey::new("g_float");
+    assert!(matches!(
+        cvars.get_key(foreign),
+        Err(CvarError::TypeMismatch { .. })
+    ));
+}
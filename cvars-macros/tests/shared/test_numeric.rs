@@ -0,0 +1,97 @@
+mod shared;
+
+use cvars::CvarError;
+
+use shared::Cvars;
+
+fn main() {
+    let mut cvars = Cvars::default();
+
+    // Integer literals are i32 and float literals f64 but they're converted to the cvar's type.
+    cvars.set_numeric("g_usize", 5).unwrap();
+    assert_eq!(cvars.g_usize, 5);
+    cvars.set_numeric("g_usize", 6.0).unwrap();
+    assert_eq!(cvars.g_usize, 6);
+    cvars.set_numeric("g_int", 7_u64).unwrap();
+    assert_eq!(cvars.g_int, 7);
+    cvars.set_numeric("g_float", 0.1).unwrap();
+    assert_eq!(cvars.g_float, 0.1_f32);
+    cvars.set_numeric("g_double", 3).unwrap();
+    assert_eq!(cvars.g_double, 3.0);
+
+    assert_eq!(cvars.get_numeric::<i64>("g_usize"), Ok(6));
+    assert_eq!(cvars.get_numeric::<f64>("g_int"), Ok(7.0));
+    assert_eq!(cvars.get_numeric::<u8>("g_double"), Ok(3));
+    assert_eq!(cvars.get_numeric::<f64>("g_float"), Ok(0.1_f32 as f64));
+
+    // Values which don't fit are rejected and the cvar is left unchanged.
+    assert_eq!(
+        cvars.set_numeric("g_usize", -1),
+        Err(CvarError::Invalid {
+            name: "g_usize".to_owned(),
+            value: "-1".to_owned(),
+            message: "out of range for usize".to_owned(),
+        })
+    );
+    assert_eq!(
+        cvars.set_numeric("g_int", 1.5),
+        Err(CvarError::Invalid {
+            name: "g_int".to_owned(),
+            value: "1.5".to_owned(),
+            message: "not a whole number, expected i32".to_owned(),
+        })
+    );
+    assert!(matches!(
+        cvars.set_numeric("g_int", f64::NAN),
+        Err(CvarError::Invalid { .. })
+    ));
+    assert!(matches!(
+        cvars.set_numeric("g_int", 1e10),
+        Err(CvarError::Invalid { .. })
+    ));
+    assert!(matches!(
+        cvars.set_numeric("g_float", 1e300),
+        Err(CvarError::Invalid { .. })
+    ));
+    assert_eq!(
+        cvars.set_numeric("g_double", u64::MAX),
+        Err(CvarError::Invalid {
+            name: "g_double".to_owned(),
+            value: "18446744073709551615".to_owned(),
+            message: "can't be represented exactly as f64".to_owned(),
+        })
+    );
+    assert_eq!(cvars.g_usize, 6);
+    assert_eq!(cvars.g_int, 7);
+    assert!(matches!(cvars.get_numeric::<u8>("g_usize"), Ok(6)));
+    cvars.g_usize = 300;
+    assert!(matches!(
+        cvars.get_numeric::<u8>("g_usize"),
+        Err(CvarError::Invalid { .. })
+    ));
+
+    // Only numeric cvars can be used.
+    assert_eq!(
+        cvars.set_numeric("g_bool", 1),
+        Err(CvarError::TypeMismatch {
+            name: "g_bool".to_owned(),
+            expected: "bool",
+            found: "i32",
+        })
+    );
+    // Both types are formatted the same way, using `type_name`.
+    assert_eq!(
+        cvars.get_numeric::<f64>("g_string"),
+        Err(CvarError::TypeMismatch {
+            name: "g_string".to_owned(),
+            expected: "alloc::string::String",
+            found: "f64",
+        })
+    );
+    assert_eq!(
+        cvars.set_numeric("g_bla", 1),
+        Err(CvarError::NotFound {
+            name: "g_bla".to_owned()
+        })
+    );
+}
@@ -0,0 +1,57 @@
+mod shared;
+
+use std::sync::{Arc, Mutex};
+
+use cvars::SetGet;
+
+use shared::{Cvars, Enum};
+
+fn main() {
+    let mut cvars = Cvars::default();
+
+    let changes = Arc::new(Mutex::new(Vec::new()));
+    let changes2 = Arc::clone(&changes);
+    cvars.observers.add("g_int", move |name, old, new| {
+        changes2.lock().unwrap().push(format!("{name} {old} {new}"));
+    });
+    let global = Arc::new(Mutex::new(Vec::new()));
+    let global2 = Arc::clone(&global);
+    cvars.observers.add_global(move |name, _old, _new| {
+        global2.lock().unwrap().push(name.to_owned());
+    });
+
+    cvars.set_str("g_int", "43").unwrap();
+    cvars.set("g_int", 44).unwrap();
+    cvars.set_str("g_enum", "one").unwrap();
+    // Failed or no-op changes don't notify
+    cvars.set_str("g_int", "bla").unwrap_err();
+    cvars.set("g_int", 44).unwrap();
+    cvars.set_str("g_bool", "true").unwrap();
+    // Neither do direct assignments
+    cvars.g_float = 6.0;
+
+    assert_eq!(*changes.lock().unwrap(), ["g_int 42 43", "g_int 43 44"]);
+    assert_eq!(*global.lock().unwrap(), ["g_int", "g_int", "g_enum"]);
+
+    cvars.reset("g_int").unwrap();
+    assert_eq!(changes.lock().unwrap().last().unwrap(), "g_int 44 42");
+
+    global.lock().unwrap().clear();
+    cvars.reset_all();
+    assert_eq!(*global.lock().unwrap(), ["g_float", "g_enum"]);
+    assert_eq!(cvars.g_enum, Enum::Two);
+
+    // Also through the trait
+    global.lock().unwrap().clear();
+    let dyn_cvars: &mut dyn SetGet = &mut cvars;
+    dyn_cvars.set_str("g_string", "changed").unwrap();
+    dyn_cvars.observers_mut().unwrap().remove("g_int");
+    dyn_cvars.set_str("g_int", "0").unwrap();
+    assert!(dyn_cvars.observers().unwrap().is_observed("g_int"));
+    dyn_cvars.observers_mut().unwrap().clear();
+    dyn_cvars.set_str("g_int", "1").unwrap();
+    assert!(dyn_cvars.observers().unwrap().is_empty());
+
+    assert_eq!(*global.lock().unwrap(), ["g_string", "g_int"]);
+    assert_eq!(changes.lock().unwrap().len(), 3);
+}
@@ -0,0 +1,52 @@
+mod shared;
+
+use std::any::Any;
+
+use cvars::{CvarError, SetGet};
+
+use shared::{Cvars, Enum};
+
+fn main() {
+    let mut cvars = Cvars::default();
+
+    assert_eq!(cvars.get_ref::<String>("g_string").unwrap(), "String");
+    assert_eq!(*cvars.get_ref::<Enum>("g_enum").unwrap(), Enum::Two);
+    assert_eq!(
+        cvars.get_ref::<i32>("bla"),
+        Err(CvarError::NotFound {
+            name: "bla".to_owned()
+        })
+    );
+    assert_eq!(
+        cvars.get_ref::<i32>("g_float"),
+        Err(CvarError::TypeMismatch {
+            name: "g_float".to_owned(),
+            expected: "f32",
+            found: "i32",
+        })
+    );
+    assert!(cvars.get_ref::<i32>("g_skipped").is_err());
+
+    cvars.get_mut::<String>("g_string").unwrap().push_str(" changed");
+    assert_eq!(cvars.g_string, "String changed");
+    *cvars.get_mut::<usize>("g_usize").unwrap() += 1;
+    assert_eq!(cvars.g_usize, 987655);
+    assert!(matches!(
+        cvars.get_mut::<f64>("g_float"),
+        Err(CvarError::TypeMismatch { .. })
+    ));
+
+    // Through the trait
+    let dyn_cvars: &mut dyn SetGet = &mut cvars;
+    let any: &dyn Any = dyn_cvars.get_any("g_int").unwrap();
+    assert_eq!(any.downcast_ref::<i32>(), Some(&42));
+    assert!(any.downcast_ref::<u32>().is_none());
+    *dyn_cvars
+        .get_any_mut("g_bool")
+        .unwrap()
+        .downcast_mut::<bool>()
+        .unwrap() = false;
+    assert!(dyn_cvars.get_any("bla").is_none());
+    assert!(dyn_cvars.get_any_mut("g_skipped").is_none());
+    assert!(!cvars.g_bool);
+}
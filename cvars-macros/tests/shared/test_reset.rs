@@ -0,0 +1,51 @@
+mod shared;
+
+use cvars::{CvarError, SetGet};
+
+use shared::{Cvars, Enum};
+
+fn main() {
+    let mut cvars = Cvars::default();
+
+    cvars.g_int = 43;
+    cvars.g_enum = Enum::One;
+    cvars.g_string = "Changed".to_owned();
+    cvars.g_skipped = 667;
+
+    assert_eq!(cvars.default_string("g_int"), Ok("42".to_owned()));
+    assert_eq!(cvars.default_string("g_string"), Ok("String".to_owned()));
+    assert_eq!(
+        cvars.default_string("g_skipped"),
+        Err(CvarError::NotFound {
+            name: "g_skipped".to_owned()
+        })
+    );
+
+    cvars.reset("g_int").unwrap();
+    assert_eq!(cvars.g_int, 42);
+    assert_eq!(cvars.g_enum, Enum::One);
+
+    assert_eq!(
+        cvars.reset("bla"),
+        Err(CvarError::NotFound {
+            name: "bla".to_owned()
+        })
+    );
+    assert_eq!(
+        cvars.reset("g_skipped"),
+        Err(CvarError::NotFound {
+            name: "g_skipped".to_owned()
+        })
+    );
+    assert_eq!(cvars.g_skipped, 667);
+
+    // Also through the trait
+    let dyn_cvars: &mut dyn SetGet = &mut cvars;
+    dyn_cvars.reset("g_string").unwrap();
+    assert_eq!(dyn_cvars.get_string("g_string"), Ok("String".to_owned()));
+    assert_eq!(dyn_cvars.default_string("g_enum"), Ok("Two".to_owned()));
+    dyn_cvars.reset_all();
+
+    assert_eq!(cvars.g_enum, Enum::Two);
+    assert_eq!(cvars.g_skipped, 667);
+}
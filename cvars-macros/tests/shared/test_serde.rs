@@ -0,0 +1,30 @@
+mod shared;
+
+use shared::{Cvars, Enum};
+
+fn main() {
+    let mut cvars = Cvars::default();
+    cvars.g_int = 43;
+    cvars.g_skipped = 667;
+
+    let json = serde_json::to_string(&cvars).unwrap();
+    let expected = r#"{"g_bool":true,"g_int":43,"g_usize":987654,"g_float":5.0,"g_double":10.0,"g_enum":"Two","g_string":"String"}"#;
+    assert_eq!(json, expected);
+
+    let loaded: Cvars = serde_json::from_str(&json).unwrap();
+    assert_eq!(loaded.g_int, 43);
+    assert_eq!(loaded.g_enum, Enum::Two);
+
+    // Missing cvars keep their default values, skipped fields are never deserialized.
+    let loaded: Cvars = serde_json::from_str(r#"{"g_int":44,"g_enum":"One"}"#).unwrap();
+    assert_eq!(loaded.g_int, 44);
+    assert_eq!(loaded.g_enum, Enum::One);
+    assert_eq!(loaded.g_usize, 987654);
+    assert_eq!(loaded.g_string, "String");
+    assert_eq!(loaded.g_skipped, 666);
+
+    let err = serde_json::from_str::<Cvars>(r#"{"g_skipped":1}"#).unwrap_err();
+    assert!(err.to_string().starts_with("unknown field `g_skipped`"));
+    let err = serde_json::from_str::<Cvars>(r#"{"g_int":"not a num"}"#).unwrap_err();
+    assert!(err.to_string().starts_with("invalid type: string"));
+}
@@ -1,5 +1,7 @@
 mod shared;
 
+use cvars::CvarError;
+
 use shared::Cvars;
 
 fn main() {
@@ -15,6 +17,12 @@ fn main() {
 
     assert_eq!(
         cvars.get_string("bla"),
-        Err("Cvar named bla not found".to_owned())
+        Err(CvarError::NotFound {
+            name: "bla".to_owned()
+        })
+    );
+    assert_eq!(
+        cvars.get_string("bla").unwrap_err().to_string(),
+        "Cvar named bla not found"
     );
 }
@@ -1,5 +1,7 @@
 mod shared;
 
+use cvars::CvarError;
+
 use shared::{Cvars, Enum};
 
 fn main() {
@@ -23,10 +25,23 @@ fn main() {
 
     assert_eq!(
         cvars.set_str("bla", "666"),
-        Err("Cvar named bla not found".to_owned())
+        Err(CvarError::NotFound {
+            name: "bla".to_owned()
+        })
+    );
+
+    let err = cvars.set_str("g_int", "not a num").unwrap_err();
+    assert_eq!(
+        err,
+        CvarError::Parse {
+            name: "g_int".to_owned(),
+            value: "not a num".to_owned(),
+            ty: "i32",
+            source_message: "invalid digit found in string".to_owned(),
+        }
     );
     assert_eq!(
-        cvars.set_str("g_int", "not a num"),
-        Err("failed to parse not a num as type i32: invalid digit found in string".to_owned())
+        err.to_string(),
+        "failed to parse not a num as type i32: invalid digit found in string"
     );
 }
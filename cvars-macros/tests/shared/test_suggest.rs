@@ -0,0 +1,25 @@
+mod shared;
+
+use cvars::SetGet;
+
+use shared::Cvars;
+
+fn main() {
+    let cvars = Cvars::default();
+
+    assert_eq!(cvars.suggest("g_flaot")[0], "g_float");
+    assert_eq!(cvars.suggest("G_INT")[0], "g_int");
+    assert_eq!(cvars.suggest("g_doubel"), ["g_double"]);
+    // Typing just the beginning
+    assert_eq!(cvars.suggest("g_s"), ["g_string"]);
+    // Nothing similar
+    assert_eq!(cvars.suggest("xyzzy_foo_bar"), [] as [&str; 0]);
+    // Skipped fields are not cvars
+    assert!(!cvars.suggest("g_skiped").contains(&"g_skipped"));
+    // At most 5
+    assert_eq!(cvars.suggest("g_").len(), 5);
+
+    // Also through the trait
+    let dyn_cvars: &dyn SetGet = &cvars;
+    assert_eq!(dyn_cvars.suggest("g_strign")[0], "g_string");
+}
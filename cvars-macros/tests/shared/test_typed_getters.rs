@@ -1,5 +1,7 @@
 mod shared;
 
+use cvars::CvarError;
+
 use shared::Cvars;
 
 fn main() {
@@ -17,6 +19,7 @@ fn main() {
         g_enum: cvars.get("g_enum").unwrap(),
         g_string: cvars.get("g_string").unwrap(),
         g_skipped: 0,
+        observers: Default::default(),
     };
     assert_eq!(other.g_bool, cvars.g_bool);
     assert_eq!(other.g_int, cvars.g_int);
@@ -28,6 +31,35 @@ fn main() {
 
     assert_eq!(
         cvars.get::<i32>("bla"),
-        Err("Cvar named bla with type i32 not found".to_owned())
+        Err(CvarError::NotFound {
+            name: "bla".to_owned()
+        })
+    );
+
+    let err = cvars.get::<i32>("g_float").unwrap_err();
+    assert_eq!(
+        err,
+        CvarError::TypeMismatch {
+            name: "g_float".to_owned(),
+            expected: "f32",
+            found: "i32",
+        }
+    );
+    assert_eq!(err.to_string(), "Cvar named g_float has type f32, not i32");
+    assert_eq!(
+        cvars.get::<String>("g_float"),
+        Err(CvarError::TypeMismatch {
+            name: "g_float".to_owned(),
+            expected: "f32",
+            found: "alloc::string::String",
+        })
+    );
+    assert_eq!(
+        cvars.get::<f32>("g_string"),
+        Err(CvarError::TypeMismatch {
+            name: "g_string".to_owned(),
+            expected: "alloc::string::String",
+            found: "f32",
+        })
     );
 }
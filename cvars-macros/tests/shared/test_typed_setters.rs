@@ -1,5 +1,7 @@
 mod shared;
 
+use cvars::CvarError;
+
 use shared::{Cvars, Enum};
 
 fn main() {
@@ -23,6 +25,17 @@ fn main() {
 
     assert_eq!(
         cvars.set("bla", 666),
-        Err("Cvar named bla with type i32 not found".to_owned())
+        Err(CvarError::NotFound {
+            name: "bla".to_owned()
+        })
+    );
+    assert_eq!(
+        cvars.set("g_usize", 666),
+        Err(CvarError::TypeMismatch {
+            name: "g_usize".to_owned(),
+            expected: "usize",
+            found: "i32",
+        })
     );
+    assert_eq!(cvars.g_usize, 987655);
 }
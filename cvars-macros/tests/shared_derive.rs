@@ -1,10 +1,13 @@
+use serde::{Deserialize, Serialize};
 use strum_macros::{Display, EnumString};
 
-use cvars::SetGet;
+use cvars::{Observers, SetGet};
 
 #[derive(Debug, Clone, SetGet)]
+#[cvars(serde)]
 pub struct Cvars {
     pub g_bool: bool,
+    #[cvars(flags(archive))]
     pub g_int: i32,
     pub g_usize: usize,
     // Comment - should not affect macros
@@ -12,11 +15,14 @@ pub struct Cvars {
     /// Doc comment - passed to macros as an attribute
     pub g_double: f64,
     pub g_enum: Enum,
+    #[cvars(flags(cheat, read_only, replicated, user_info))]
     pub g_string: String,
     #[warn(clippy::pedantic)] // Testing that the field can have other attributes
     #[cvars(skip)]
     #[allow(clippy::pedantic)]
     pub g_skipped: i32,
+    #[cvars(observers)]
+    pub observers: Observers,
 }
 
 impl Default for Cvars {
@@ -30,11 +36,14 @@ impl Default for Cvars {
             g_enum: Enum::Two,
             g_string: "String".to_string(),
             g_skipped: 666,
+            observers: Observers::new(),
         }
     }
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Display, EnumString)]
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Default, Display, EnumString, Serialize, Deserialize,
+)]
 #[strum(ascii_case_insensitive)]
 pub enum Enum {
     #[default]
@@ -1,11 +1,14 @@
+use serde::{Deserialize, Serialize};
 use strum_macros::{Display, EnumString};
 
-use cvars::cvars;
+use cvars::{cvars, Observers};
 
 cvars! {
     //! Inner doc comment - passed to macros as an attribute
     #![derive(Debug, Clone)]
+    #![cvars(serde)]
     g_bool: bool = true,
+    #[cvars(flags(archive))]
     g_int: i32 = 42,
     g_usize: usize = 987654,
     // Comment - should not affect macros
@@ -13,14 +16,19 @@ cvars! {
     /// Doc comment - passed to macros as an attribute
     g_double: f64 = 10.0,
     g_enum: Enum = Enum::Two,
+    #[cvars(flags(cheat, read_only, replicated, user_info))]
     g_string: String = "String".to_string(),
     #[warn(clippy::pedantic)] // Testing that the field can have other attributes
     #[cvars(skip)]
     #[allow(clippy::pedantic)]
     g_skipped: i32 = 666,
+    #[cvars(observers)]
+    observers: Observers = Observers::new(),
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Display, EnumString)]
+#[derive(
+    Debug, Clone, Copy, PartialEq, Eq, Default, Display, EnumString, Serialize, Deserialize,
+)]
 #[strum(ascii_case_insensitive)]
 pub enum Enum {
     #[default]
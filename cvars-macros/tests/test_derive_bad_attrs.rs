@@ -0,0 +1,13 @@
+use cvars::SetGet;
+
+#[derive(SetGet)]
+pub struct Cvars {
+    #[cvars(skip, hidden)]
+    g_int: i32,
+    #[cvars(flags(archive, cheats))]
+    g_float: f32,
+    #[cvars(min)]
+    g_bool: bool,
+}
+
+fn main() {}
@@ -0,0 +1,102 @@
+use cvars::{CvarError, SetGet};
+
+#[derive(SetGet)]
+pub struct Cvars {
+    #[cvars(min = 0.0, max = 10.0)]
+    g_respawn_delay: f32,
+    #[cvars(min = -5)]
+    g_score_min: i32,
+    #[cvars(one_of = ["low", "high"])]
+    r_quality: String,
+    #[cvars(one_of = [1, 2, 4], validate = validate_even)]
+    r_msaa: u32,
+    g_unconstrained: i32,
+}
+
+impl Default for Cvars {
+    fn default() -> Self {
+        Self {
+            g_respawn_delay: 2.0,
+            g_score_min: 0,
+            r_quality: "high".to_owned(),
+            r_msaa: 2,
+            g_unconstrained: 0,
+        }
+    }
+}
+
+fn validate_even(value: &u32) -> Result<(), String> {
+    if value % 2 == 0 {
+        Ok(())
+    } else {
+        Err("must be even".to_owned())
+    }
+}
+
+fn main() {
+    let mut cvars = Cvars::default();
+
+    cvars.set_str("g_respawn_delay", "10").unwrap();
+    assert_eq!(cvars.g_respawn_delay, 10.0);
+    assert_eq!(
+        cvars.set_str("g_respawn_delay", "-5"),
+        Err(CvarError::Invalid {
+            name: "g_respawn_delay".to_owned(),
+            value: "-5".to_owned(),
+            message: "must be at least 0.0".to_owned(),
+        })
+    );
+    assert_eq!(
+        cvars.set("g_respawn_delay", 10.5_f32),
+        Err(CvarError::Invalid {
+            name: "g_respawn_delay".to_owned(),
+            value: "10.5".to_owned(),
+            message: "must be at most 10.0".to_owned(),
+        })
+    );
+    assert_eq!(cvars.g_respawn_delay, 10.0);
+    // Parse errors are reported before constraints are checked
+    assert!(matches!(
+        cvars.set_str("g_respawn_delay", "bla"),
+        Err(CvarError::Parse { .. })
+    ));
+
+    cvars.set("g_score_min", -5).unwrap();
+    assert!(cvars.set("g_score_min", -6).is_err());
+
+    cvars.set_str("r_quality", "low").unwrap();
+    assert_eq!(
+        cvars.set_str("r_quality", "ultra").unwrap_err().to_string(),
+        "invalid value ultra for cvar r_quality: must be one of: low, high"
+    );
+    assert_eq!(cvars.r_quality, "low");
+
+    cvars.set_str("r_msaa", "4").unwrap();
+    assert!(cvars.set_str("r_msaa", "3").is_err());
+    assert!(cvars.set("r_msaa", 1_u32).is_err());
+    assert_eq!(cvars.r_msaa, 4);
+
+    cvars.set_str("g_unconstrained", "-1000").unwrap();
+
+    // Also through the trait
+    let dyn_cvars: &mut dyn SetGet = &mut cvars;
+    assert!(dyn_cvars.set_str("g_respawn_delay", "11").is_err());
+
+    let info = cvars.cvar_info("g_respawn_delay").unwrap();
+    assert_eq!(info.min, Some("0.0"));
+    assert_eq!(info.max, Some("10.0"));
+    assert_eq!(info.one_of, &[] as &[&str]);
+    assert_eq!(info.validate, None);
+    assert_eq!(cvars.cvar_info("g_score_min").unwrap().min, Some("-5"));
+    assert_eq!(cvars.cvar_info("r_quality").unwrap().one_of, ["low", "high"]);
+    let info = cvars.cvar_info("r_msaa").unwrap();
+    assert_eq!(info.one_of, ["1", "2", "4"]);
+    assert_eq!(info.validate, Some("validate_even"));
+    let info = cvars.cvar_info("g_unconstrained").unwrap();
+    assert_eq!(info.min, None);
+    assert_eq!(info.max, None);
+
+    assert_eq!(cvars.allowed_values("r_quality").unwrap(), ["low", "high"]);
+    assert_eq!(cvars.allowed_values("r_msaa").unwrap(), ["1", "2", "4"]);
+    assert_eq!(cvars.allowed_values("g_unconstrained").unwrap(), [] as [String; 0]);
+}
@@ -0,0 +1,13 @@
+use cvars::SetGet;
+
+#[derive(Default, SetGet)]
+pub struct Cvars {
+    g_int: i32,
+}
+
+fn main() {
+    let cvars = Cvars::default();
+
+    // Default values are specified separately when deriving.
+    assert_eq!(cvars.cvar_info("g_int").unwrap().default, None);
+}
@@ -0,0 +1,105 @@
+use std::{fmt::Display, str::FromStr};
+
+use cvars::{CvarError, SetGet};
+
+pub trait Float: Copy + Default + FromStr + Display + 'static {}
+
+impl Float for f32 {}
+impl Float for f64 {}
+
+// Type parameter with a bound
+#[derive(Debug, Clone, Default, SetGet)]
+pub struct Tuning<T: Float> {
+    pub g_gravity: T,
+    pub g_friction: T,
+    pub g_iterations: u32,
+}
+
+// Bounds in a where clause, the struct itself adds the bounds required by the macro
+#[derive(Debug, Clone, Default, SetGet)]
+pub struct WhereClause<T>
+where
+    T: Clone + Default,
+{
+    pub g_value: T,
+}
+
+// Lifetimes - fields with non-'static types can't be cvars but can be skipped
+#[derive(Debug, Clone, Default, SetGet)]
+pub struct WithLifetime<'a> {
+    pub g_int: i32,
+    #[cvars(skip)]
+    pub name: &'a str,
+}
+
+// Const generics
+#[derive(Debug, Clone, SetGet)]
+pub struct WithConst<const N: usize> {
+    pub g_int: i32,
+    #[cvars(skip)]
+    pub array: [i32; N],
+}
+
+impl<const N: usize> Default for WithConst<N> {
+    fn default() -> Self {
+        Self {
+            g_int: 42,
+            array: [0; N],
+        }
+    }
+}
+
+fn main() {
+    let mut tuning32 = Tuning::<f32>::default();
+    tuning32.set_str("g_gravity", "9.81").unwrap();
+    tuning32.set("g_friction", 0.5_f32).unwrap();
+    assert_eq!(tuning32.g_gravity, 9.81_f32);
+    assert_eq!(tuning32.get::<f32>("g_friction"), Ok(0.5));
+    assert_eq!(
+        tuning32.set("g_friction", 0.5_f64),
+        Err(CvarError::TypeMismatch {
+            name: "g_friction".to_owned(),
+            expected: "f32",
+            found: "f64",
+        })
+    );
+    assert_eq!(tuning32.cvar_info("g_gravity").unwrap().ty, "T");
+    assert_eq!(tuning32.cvar_info("g_gravity").unwrap().type_name(), "f32");
+
+    let mut tuning64 = Tuning::<f64>::default();
+    tuning64.set("g_gravity", 9.81).unwrap();
+    tuning64.set_str("g_iterations", "10").unwrap();
+    assert_eq!(tuning64.g_gravity, 9.81);
+    tuning64.reset_all();
+    assert_eq!(tuning64.g_iterations, 0);
+
+    let mut where_clause = WhereClause::<String>::default();
+    where_clause.set_str("g_value", "hello").unwrap();
+    assert_eq!(where_clause.get::<String>("g_value").unwrap(), "hello");
+
+    let name = String::from("temporary");
+    let mut with_lifetime = WithLifetime {
+        g_int: 0,
+        name: &name,
+    };
+    with_lifetime.set_str("g_int", "5").unwrap();
+    assert_eq!(with_lifetime.get::<i32>("g_int"), Ok(5));
+    assert_eq!(with_lifetime.cvar_count(), 1);
+    assert_eq!(with_lifetime.name, "temporary");
+
+    let mut with_const = WithConst::<3>::default();
+    with_const.set("g_int", 43).unwrap();
+    assert_eq!(with_const.changed()[0].default, "42");
+
+    // Also through the trait
+    let all: [&mut dyn SetGet; 4] = [
+        &mut tuning32,
+        &mut tuning64,
+        &mut with_lifetime,
+        &mut with_const,
+    ];
+    for cvars in all {
+        assert!(cvars.cvar_count() > 0);
+        assert!(cvars.set_str("bla", "1").is_err());
+    }
+}
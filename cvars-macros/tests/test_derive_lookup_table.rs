@@ -0,0 +1,75 @@
+use cvars::{CvarError, SetGet};
+
+#[derive(SetGet)]
+#[cvars(lookup_table)]
+pub struct Cvars {
+    sv_hostname: String,
+    g_rocket_damage: f32,
+    #[cvars(skip)]
+    g_skipped: i32,
+    #[cvars(nested)]
+    cl: ClientCvars,
+    g_rocket: bool,
+}
+
+impl Default for Cvars {
+    fn default() -> Self {
+        Self {
+            sv_hostname: "Unnamed".to_owned(),
+            g_rocket_damage: 100.0,
+            g_skipped: 0,
+            cl: ClientCvars::default(),
+            g_rocket: true,
+        }
+    }
+}
+
+#[derive(SetGet)]
+#[cvars(lookup_table)]
+pub struct ClientCvars {
+    fov: f32,
+    sensitivity: f32,
+}
+
+impl Default for ClientCvars {
+    fn default() -> Self {
+        Self {
+            fov: 90.0,
+            sensitivity: 1.0,
+        }
+    }
+}
+
+fn main() {
+    let mut cvars = Cvars::default();
+
+    assert_eq!(cvars.get_string("sv_hostname").unwrap(), "Unnamed");
+    assert_eq!(cvars.get_string("cl.fov").unwrap(), "90");
+    assert_eq!(cvars.get::<bool>("g_rocket").unwrap(), true);
+
+    cvars.set_str("g_rocket_damage", "150").unwrap();
+    assert_eq!(cvars.g_rocket_damage, 150.0);
+    cvars.set("cl.sensitivity", 2.5_f32).unwrap();
+    assert_eq!(cvars.cl.sensitivity, 2.5);
+
+    cvars.reset_all();
+    assert_eq!(cvars.g_rocket_damage, 100.0);
+    assert_eq!(cvars.cl.sensitivity, 1.0);
+
+    assert_eq!(
+        cvars.get_string("g_skipped"),
+        Err(CvarError::NotFound {
+            name: "g_skipped".to_owned()
+        })
+    );
+    assert_eq!(cvars.cvar_info("cl.fov").unwrap().name, "cl.fov");
+    assert_eq!(cvars.cvar_info("g_rocket").unwrap().name, "g_rocket");
+    assert_eq!(cvars.cvar_info("g_skipped"), None);
+    assert_eq!(cvars.cvar_info("cl.fovv"), None);
+    assert_eq!(
+        cvars.get_string("cl.fovv"),
+        Err(CvarError::NotFound {
+            name: "cl.fovv".to_owned()
+        })
+    );
+}
@@ -0,0 +1,30 @@
+use cvars::SetGet;
+
+#[derive(SetGet, Default)]
+#[cvars(name_enum)]
+pub struct Cvars {
+    g_rocket_damage: f32,
+    r_vsync: bool,
+    #[cvars(nested)]
+    cl: ClientCvars,
+}
+
+#[derive(SetGet, Default)]
+#[cvars(name_enum = ClientCvarName)]
+pub struct ClientCvars {
+    fov: f32,
+    r#type: i32,
+}
+
+fn main() {
+    assert_eq!(CvarName::ALL, [CvarName::GRocketDamage, CvarName::RVsync]);
+    assert_eq!(ClientCvarName::ALL, [ClientCvarName::Fov, ClientCvarName::Type]);
+    assert_eq!(ClientCvarName::Type.as_str(), "r#type");
+
+    let mut cvars = Cvars::default();
+    cvars.set_str("r_vsync", "true").unwrap();
+    assert!(cvars.r_vsync);
+    cvars.set_str("cl.fov", "90").unwrap();
+    assert_eq!(cvars.cl.fov, 90.0);
+    assert_eq!(cvars.get_string("cl.r#type").unwrap(), "0");
+}
@@ -0,0 +1,36 @@
+use cvars::{CvarError, SetGet};
+
+#[derive(Debug, Clone, Default, SetGet)]
+pub struct ServerConfig {
+    pub sv_hostname: String,
+    pub sv_maxplayers: u32,
+}
+
+#[derive(Debug, Clone, Default, SetGet)]
+struct ClientConfig {
+    cl_fov: f32,
+    cl_name: String,
+}
+
+fn main() {
+    let mut server = ServerConfig::default();
+    let mut client = ClientConfig::default();
+
+    server.set("sv_maxplayers", 16_u32).unwrap();
+    assert_eq!(server.get::<u32>("sv_maxplayers"), Ok(16));
+    assert_eq!(
+        server.get::<String>("sv_maxplayers"),
+        Err(CvarError::TypeMismatch {
+            name: "sv_maxplayers".to_owned(),
+            expected: "u32",
+            found: "alloc::string::String",
+        })
+    );
+    client.set_str("cl_name", "Player").unwrap();
+    client.set("cl_fov", 100.0_f32).unwrap();
+    assert_eq!(client.get::<String>("cl_name").unwrap(), "Player");
+    assert_eq!(client.cl_fov, 100.0);
+
+    let dyn_server: &dyn SetGet = &server;
+    assert_eq!(dyn_server.get_string("sv_maxplayers").unwrap(), "16");
+}
@@ -1,6 +1,6 @@
 use cvars::SetGet;
 
-#[derive(SetGet)]
+#[derive(Default, SetGet)]
 #[cvars(sorted)]
 pub struct Cvars {
     a: i32,
@@ -0,0 +1,29 @@
+use cvars::SetGet;
+
+#[derive(SetGet)]
+pub struct TupleCvars(i32, f32);
+
+#[derive(SetGet)]
+pub struct UnitCvars;
+
+#[derive(SetGet)]
+pub enum EnumCvars {
+    A,
+    B,
+}
+
+#[derive(SetGet)]
+#[cvars(name = OtherCvars)]
+pub struct NamedCvars {
+    g_int: i32,
+}
+
+#[derive(SetGet)]
+pub struct SkippedCvars {
+    #[cvars(skip)]
+    g_int: i32,
+    #[cvars(skip)]
+    g_float: f32,
+}
+
+fn main() {}
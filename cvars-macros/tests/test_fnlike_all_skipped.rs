@@ -0,0 +1,8 @@
+use cvars::cvars;
+
+cvars! {
+    #[cvars(skip)]
+    g_int: i32 = 42,
+}
+
+fn main() {}
@@ -0,0 +1,16 @@
+use cvars::cvars;
+
+cvars! {
+    #![cvars(sorted, shuffled)]
+
+    g_int: i32 = 42,
+}
+
+cvars! {
+    #![cvars(name = OtherCvars)]
+
+    #[cvars(flags(cheat, debug))]
+    g_float: f32 = 1.0,
+}
+
+fn main() {}
@@ -0,0 +1,135 @@
+use cvars::cvars;
+
+// `cfg(all())` is always enabled, `cfg(any())` is always disabled.
+
+cvars! {
+    #![cvars(serde)]
+
+    g_always: i32 = 1,
+    #[cfg(all())]
+    g_enabled: i32 = 2,
+    #[cfg(any())]
+    #[cvars(min = 0)]
+    g_disabled: i32 = 3,
+    #[cfg(any())]
+    g_two_versions: f32 = 4.0,
+    #[cfg(all())]
+    g_two_versions: i32 = 5,
+    #[cfg(any())]
+    #[cvars(nested)]
+    disabled: DisabledCvars = DisabledCvars::default(),
+    #[cfg(all())]
+    #[cvars(nested)]
+    enabled: EnabledCvars = EnabledCvars::default(),
+}
+
+cvars! {
+    #![cvars(name = OtherCvars, name_enum, lookup_table)]
+
+    r_always: bool = true,
+    #[cfg(any())]
+    r_disabled: bool = false,
+    #[cfg(all())]
+    r_enabled: bool = false,
+    #[cfg_attr(all(), cvars(flags(cheat)))]
+    r_cheat: bool = false,
+    #[cfg_attr(any(), cvars(min = 100))]
+    r_fov: i32 = 90,
+    #[cfg_attr(all(), cvars(min = 0), doc = "Both attributes apply.")]
+    #[cfg_attr(all(), cfg_attr(all(), cvars(max = 5)))]
+    r_lod: i32 = 1,
+}
+
+cvars! {
+    #![cvars(name = EnabledCvars, lookup_table, serde)]
+
+    a: i32 = 6,
+    #[cfg(any())]
+    b: i32 = 7,
+}
+
+cvars! {
+    #![cvars(name = DisabledCvars, sorted)]
+
+    c: i32 = 8,
+    // Expanded into two versions of `d` which must not be reported as unsorted.
+    #[cfg_attr(all(), cvars(min = 0))]
+    d: i32 = 9,
+}
+
+// Typed getters only accept `f64` here, see test_fnlike_wrong_type.rs.
+cvars! {
+    #![cvars(name = TypedCvars)]
+
+    #[cfg(any())]
+    x: f32 = 10.0,
+    #[cfg(all())]
+    x: f64 = 11.0,
+}
+
+fn main() {
+    let mut cvars = Cvars::default();
+
+    assert_eq!(Cvars::CVAR_COUNT, 4);
+    assert_eq!(cvars.cvar_count(), 4);
+    let names: Vec<_> = cvars.cvar_infos().iter().map(|info| info.name).collect();
+    assert_eq!(names, ["g_always", "g_enabled", "g_two_versions", "enabled.a"]);
+
+    assert_eq!(cvars.get_string("g_enabled").unwrap(), "2");
+    assert!(cvars.get_string("g_disabled").is_err());
+    cvars.set_str("g_disabled", "-1").unwrap_err();
+    cvars.set("g_two_versions", 50).unwrap();
+    assert_eq!(cvars.get_key(Cvars::G_TWO_VERSIONS).unwrap(), 50);
+    cvars.reset("g_two_versions").unwrap();
+    assert_eq!(cvars.g_two_versions, 5);
+    cvars.set_str("enabled.a", "60").unwrap();
+    assert!(cvars.set_str("enabled.b", "70").is_err());
+    assert!(cvars.set_str("disabled.c", "80").is_err());
+    cvars.reset_all();
+    assert_eq!(cvars.enabled.a, 6);
+
+    let json = serde_json::to_string(&cvars).unwrap();
+    assert_eq!(
+        json,
+        r#"{"g_always":1,"g_enabled":2,"g_two_versions":5,"enabled":{"a":6}}"#
+    );
+    let cvars: Cvars = serde_json::from_str(r#"{"g_enabled":20}"#).unwrap();
+    assert_eq!(cvars.g_enabled, 20);
+    assert!(serde_json::from_str::<Cvars>(r#"{"g_disabled":30}"#).is_err());
+
+    let mut other = OtherCvars::default();
+    assert_eq!(
+        CvarName::ALL,
+        [
+            CvarName::RAlways,
+            CvarName::REnabled,
+            CvarName::RCheat,
+            CvarName::RFov,
+            CvarName::RLod
+        ]
+    );
+    assert_eq!(other.cvar_count(), 5);
+    other.set_str("r_enabled", "true").unwrap();
+    assert!(other.r_enabled);
+    assert!(other.set_str("r_disabled", "true").is_err());
+    assert!("r_disabled".parse::<CvarName>().is_err());
+    assert_eq!(other.cvar_info("r_disabled"), None);
+    for info in other.cvar_infos() {
+        assert_eq!(other.cvar_info(info.name), Some(info));
+    }
+
+    // `cvars(...)` inside `cfg_attr` only applies when the predicate is true.
+    assert!(other.cvar_flags("r_cheat").unwrap().cheat);
+    other.set("r_fov", 50).unwrap();
+    let info = other.cvar_info("r_lod").unwrap();
+    assert_eq!(info.min, Some("0"));
+    assert_eq!(info.max, Some("5"));
+    assert_eq!(info.doc, "Both attributes apply.");
+    assert!(other.set("r_lod", -1).is_err());
+    assert!(other.set("r_lod", 6).is_err());
+    other.set("r_lod", 5).unwrap();
+
+    let mut typed = TypedCvars::default();
+    typed.set("x", 12.0).unwrap();
+    assert_eq!(typed.get::<f64>("x"), Ok(12.0));
+}
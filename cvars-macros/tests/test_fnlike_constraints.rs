@@ -0,0 +1,178 @@
+use std::{fmt, str::FromStr};
+
+use cvars::{cvars, CvarError, SetGet};
+use serde::{Deserialize, Serialize};
+
+cvars! {
+    #![cvars(serde)]
+    #[cvars(min = 0.0, max = 10.0)]
+    g_respawn_delay: f32 = 2.0,
+    #[cvars(min = -5)]
+    g_score_min: i32 = 0,
+    #[cvars(one_of = ["low", "high"])]
+    r_quality: String = "high".to_owned(),
+    #[cvars(one_of = [1, 2, 4], validate = validate_even)]
+    r_msaa: u32 = 2,
+    g_unconstrained: i32 = 0,
+    #[cvars(one_of = [Split::Vertical, Split::Horizontal])]
+    cl_split: Split = Split::Vertical,
+    #[cvars(min = 60.0, max = MAX_FOV)]
+    cl_fov: f32 = 90.0,
+}
+
+const MAX_FOV: f32 = 130.0;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+enum Split {
+    Vertical,
+    Horizontal,
+    Grid,
+}
+
+impl FromStr for Split {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "vertical" => Ok(Split::Vertical),
+            "horizontal" => Ok(Split::Horizontal),
+            "grid" => Ok(Split::Grid),
+            _ => Err(format!("unknown split {s}")),
+        }
+    }
+}
+
+impl fmt::Display for Split {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Split::Vertical => write!(f, "vertical"),
+            Split::Horizontal => write!(f, "horizontal"),
+            Split::Grid => write!(f, "grid"),
+        }
+    }
+}
+
+fn validate_even(value: &u32) -> Result<(), String> {
+    if value % 2 == 0 {
+        Ok(())
+    } else {
+        Err("must be even".to_owned())
+    }
+}
+
+fn main() {
+    let mut cvars = Cvars::default();
+
+    cvars.set_str("g_respawn_delay", "10").unwrap();
+    assert_eq!(cvars.g_respawn_delay, 10.0);
+    assert_eq!(
+        cvars.set_str("g_respawn_delay", "-5"),
+        Err(CvarError::Invalid {
+            name: "g_respawn_delay".to_owned(),
+            value: "-5".to_owned(),
+            message: "must be at least 0.0".to_owned(),
+        })
+    );
+    assert_eq!(
+        cvars.set("g_respawn_delay", 10.5_f32),
+        Err(CvarError::Invalid {
+            name: "g_respawn_delay".to_owned(),
+            value: "10.5".to_owned(),
+            message: "must be at most 10.0".to_owned(),
+        })
+    );
+    assert_eq!(cvars.g_respawn_delay, 10.0);
+    // Parse errors are reported before constraints are checked
+    assert!(matches!(
+        cvars.set_str("g_respawn_delay", "bla"),
+        Err(CvarError::Parse { .. })
+    ));
+
+    cvars.set("g_score_min", -5).unwrap();
+    assert!(cvars.set("g_score_min", -6).is_err());
+
+    cvars.set_str("r_quality", "low").unwrap();
+    assert_eq!(
+        cvars.set_str("r_quality", "ultra").unwrap_err().to_string(),
+        "invalid value ultra for cvar r_quality: must be one of: low, high"
+    );
+    assert_eq!(cvars.r_quality, "low");
+
+    cvars.set_str("r_msaa", "4").unwrap();
+    assert!(cvars.set_str("r_msaa", "3").is_err());
+    assert!(cvars.set("r_msaa", 1_u32).is_err());
+    assert_eq!(cvars.r_msaa, 4);
+
+    cvars.set_str("g_unconstrained", "-1000").unwrap();
+
+    // Also through the trait
+    let dyn_cvars: &mut dyn SetGet = &mut cvars;
+    assert!(dyn_cvars.set_str("g_respawn_delay", "11").is_err());
+
+    let info = cvars.cvar_info("g_respawn_delay").unwrap();
+    assert_eq!(info.min, Some("0.0"));
+    assert_eq!(info.max, Some("10.0"));
+    assert_eq!(info.one_of, &[] as &[&str]);
+    assert_eq!(info.validate, None);
+    assert_eq!(cvars.cvar_info("g_score_min").unwrap().min, Some("-5"));
+    assert_eq!(
+        cvars.cvar_info("r_quality").unwrap().one_of,
+        ["low", "high"]
+    );
+    let info = cvars.cvar_info("r_msaa").unwrap();
+    assert_eq!(info.one_of, ["1", "2", "4"]);
+    assert_eq!(info.validate, Some("validate_even"));
+    let info = cvars.cvar_info("g_unconstrained").unwrap();
+    assert_eq!(info.min, None);
+    assert_eq!(info.max, None);
+
+    // `one_of` in `CvarInfo` is the source code, `allowed_values` is what can be typed in.
+    cvars.set_str("cl_split", "horizontal").unwrap();
+    assert!(cvars.set_str("cl_split", "grid").is_err());
+    assert_eq!(
+        cvars.cvar_info("cl_split").unwrap().one_of,
+        ["Split :: Vertical", "Split :: Horizontal"]
+    );
+    assert_eq!(
+        cvars.allowed_values("cl_split"),
+        Ok(vec!["vertical".to_owned(), "horizontal".to_owned()])
+    );
+    assert_eq!(cvars.allowed_values("r_msaa").unwrap(), ["1", "2", "4"]);
+    assert_eq!(
+        cvars.allowed_values("g_unconstrained").unwrap(),
+        [] as [String; 0]
+    );
+
+    // `min` and `max` in `CvarInfo` are the source code, `cvar_range` is the evaluated bounds.
+    assert_eq!(cvars.cvar_info("cl_fov").unwrap().max, Some("MAX_FOV"));
+    assert_eq!(cvars.cvar_range("cl_fov"), Some((60.0, 130.0)));
+    assert_eq!(cvars.cvar_range("g_respawn_delay"), Some((0.0, 10.0)));
+    assert_eq!(cvars.cvar_range("g_score_min"), Some((-5.0, f64::INFINITY)));
+    assert_eq!(cvars.cvar_range("g_unconstrained"), None);
+    assert_eq!(cvars.cvar_range("r_quality"), None);
+    assert_eq!(cvars.cvar_range("g_bla"), None);
+    let dyn_cvars: &dyn SetGet = &cvars;
+    assert_eq!(dyn_cvars.cvar_range("cl_fov"), Some((60.0, 130.0)));
+
+    // Deserializing checks the constraints too.
+    let cvars: Cvars = serde_json::from_str(r#"{"g_respawn_delay": 5.0, "r_msaa": 4}"#).unwrap();
+    assert_eq!(cvars.g_respawn_delay, 5.0);
+    assert_eq!(cvars.r_msaa, 4);
+    let err = serde_json::from_str::<Cvars>(r#"{"g_respawn_delay": 9000.0}"#)
+        .err()
+        .unwrap();
+    assert!(err
+        .to_string()
+        .starts_with("invalid value 9000 for cvar g_respawn_delay: must be at most 10.0"));
+    assert!(serde_json::from_str::<Cvars>(r#"{"r_msaa": 8}"#).is_err());
+    assert!(serde_json::from_str::<Cvars>(r#"{"r_msaa": 1}"#).is_err());
+    assert!(serde_json::from_str::<Cvars>(r#"{"cl_split": "Grid"}"#).is_err());
+    assert!(serde_json::from_str::<Cvars>(r#"{"g_unconstrained": -9000}"#).is_ok());
+
+    assert_eq!(
+        cvars.allowed_values("g_bla"),
+        Err(CvarError::NotFound {
+            name: "g_bla".to_owned()
+        })
+    );
+}
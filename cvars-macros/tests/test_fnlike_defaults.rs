@@ -0,0 +1,18 @@
+use cvars::cvars;
+
+cvars! {
+    g_int: i32 = 42,
+    g_float: f32 = 5.0 * 2.0,
+    g_string: String = "String".to_string(),
+}
+
+fn main() {
+    let cvars = Cvars::default();
+
+    assert_eq!(cvars.cvar_info("g_int").unwrap().default, Some("42"));
+    assert_eq!(cvars.cvar_info("g_float").unwrap().default, Some("5.0 * 2.0"));
+    assert_eq!(
+        cvars.cvar_info("g_string").unwrap().default,
+        Some("\"String\".to_string()")
+    );
+}
@@ -0,0 +1,9 @@
+use cvars::cvars;
+
+cvars! {
+    g_int: i32 = 42,
+    g_float: f32 = 1.0,
+    g_int: i32 = 43,
+}
+
+fn main() {}
@@ -0,0 +1,14 @@
+use cvars::cvars;
+
+cvars! {
+    g_damage: f32 = 50.0,
+    G_DAMAGE: f32 = 50.0,
+}
+
+cvars! {
+    #![cvars(name = OtherCvars)]
+
+    cvar_count: i32 = 0,
+}
+
+fn main() {}
@@ -0,0 +1,57 @@
+use cvars::{cvars, CvarError};
+
+cvars! {
+    #![cvars(lookup_table)]
+
+    sv_hostname: String = "Unnamed".to_owned(),
+    g_rocket_damage: f32 = 100.0,
+    #[cvars(min = 0)]
+    g_rocket_ammo: i32 = 20,
+    cl_fov: f32 = 90.0,
+    g_rocket: bool = true,
+}
+
+fn main() {
+    let mut cvars = Cvars::default();
+
+    assert_eq!(cvars.get_string("sv_hostname").unwrap(), "Unnamed");
+    assert_eq!(cvars.get_string("g_rocket").unwrap(), "true");
+    assert_eq!(cvars.get::<i32>("g_rocket_ammo").unwrap(), 20);
+    assert_eq!(cvars.get::<f32>("cl_fov").unwrap(), 90.0);
+
+    cvars.set_str("g_rocket_damage", "150").unwrap();
+    assert_eq!(cvars.g_rocket_damage, 150.0);
+    cvars.set("sv_hostname", "My Server".to_owned()).unwrap();
+    assert_eq!(cvars.sv_hostname, "My Server");
+    cvars.set("g_rocket", false).unwrap();
+    assert!(!cvars.g_rocket);
+
+    assert!(matches!(
+        cvars.set_str("g_rocket_ammo", "-1"),
+        Err(CvarError::Invalid { .. })
+    ));
+    assert!(matches!(
+        cvars.set("g_rocket_ammo", 1.0_f32),
+        Err(CvarError::TypeMismatch { .. })
+    ));
+
+    cvars.reset("g_rocket_damage").unwrap();
+    assert_eq!(cvars.g_rocket_damage, 100.0);
+
+    for name in ["", "a", "g_rocket_", "g_rocket_damagee", "zz", "G_ROCKET"] {
+        let not_found = Err(CvarError::NotFound {
+            name: name.to_owned(),
+        });
+        assert_eq!(cvars.get_string(name), not_found);
+        assert_eq!(cvars.set_str(name, "1"), not_found.map(|_| ()));
+        assert_eq!(cvars.reset(name), Err(CvarError::NotFound { name: name.to_owned() }));
+        assert_eq!(cvars.cvar_info(name), None);
+        assert_eq!(cvars.cvar_flags(name), None);
+    }
+
+    for info in cvars.cvar_infos() {
+        assert!(cvars.get_string(info.name).is_ok());
+        assert_eq!(cvars.cvar_info(info.name), Some(info));
+    }
+    assert_eq!(cvars.cvar_info("g_rocket_ammo").unwrap().min, Some("0"));
+}
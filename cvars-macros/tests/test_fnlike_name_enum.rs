@@ -0,0 +1,76 @@
+use std::str::FromStr;
+
+use cvars::{cvars, CvarError};
+
+cvars! {
+    #![cvars(name_enum)]
+
+    g_rocket_damage: f32 = 100.0,
+    sv_hostname: String = "Unnamed".to_owned(),
+    #[cvars(skip)]
+    g_skipped: i32 = 0,
+    cl_fov: f32 = 90.0,
+}
+
+cvars! {
+    #![cvars(name = OtherCvars, name_enum = OtherCvarName, lookup_table)]
+
+    r_vsync: bool = true,
+    r_fullscreen: bool = false,
+}
+
+fn describe(name: CvarName) -> &'static str {
+    match name {
+        CvarName::GRocketDamage => "gameplay",
+        CvarName::SvHostname => "server",
+        CvarName::ClFov => "client",
+    }
+}
+
+fn main() {
+    assert_eq!(
+        CvarName::ALL,
+        [
+            CvarName::GRocketDamage,
+            CvarName::SvHostname,
+            CvarName::ClFov
+        ]
+    );
+    assert_eq!(CvarName::ClFov.as_str(), "cl_fov");
+    assert_eq!(CvarName::SvHostname.to_string(), "sv_hostname");
+    assert_eq!(
+        CvarName::from_str("g_rocket_damage"),
+        Ok(CvarName::GRocketDamage)
+    );
+    assert_eq!(
+        "g_skipped".parse::<CvarName>(),
+        Err(CvarError::NotFound {
+            name: "g_skipped".to_owned()
+        })
+    );
+    for &name in CvarName::ALL {
+        assert_eq!(name.as_str().parse::<CvarName>(), Ok(name));
+    }
+    assert_eq!(describe(CvarName::ClFov), "client");
+
+    let mut cvars = Cvars::default();
+    cvars.set_str("g_rocket_damage", "150").unwrap();
+    assert_eq!(cvars.g_rocket_damage, 150.0);
+    assert_eq!(cvars.get_string(CvarName::SvHostname.as_str()).unwrap(), "Unnamed");
+    cvars.set("cl_fov", 100.0_f32).unwrap();
+    assert_eq!(cvars.get::<f32>("cl_fov").unwrap(), 100.0);
+    cvars.reset("cl_fov").unwrap();
+    assert_eq!(cvars.cl_fov, 90.0);
+    assert!(cvars.get_string("g_skipped").is_err());
+
+    assert_eq!(
+        OtherCvarName::ALL,
+        [OtherCvarName::RVsync, OtherCvarName::RFullscreen]
+    );
+    assert_eq!("r_vsync".parse(), Ok(OtherCvarName::RVsync));
+    assert!("r_vsyn".parse::<OtherCvarName>().is_err());
+    let mut other = OtherCvars::default();
+    other.set_str("r_fullscreen", "true").unwrap();
+    assert!(other.r_fullscreen);
+    assert!(other.set_str("r_vsyncc", "true").is_err());
+}
@@ -0,0 +1,45 @@
+mod inner {
+    use cvars::cvars;
+
+    cvars! {
+        #![derive(Debug, Clone)]
+        #![cvars(name = ClientCvars, vis = pub(crate))]
+        cl_fov: f32 = 90.0,
+    }
+}
+
+use cvars::{cvars, SetGet};
+
+use inner::ClientCvars;
+
+cvars! {
+    #![cvars(name = ServerCvars)]
+    sv_hostname: String = "Unnamed".to_owned(),
+    sv_fov: f32 = 90.0,
+}
+
+// Multiple structs in one module with the default name in a different module
+mod third {
+    use cvars::cvars;
+
+    cvars! {
+        g_int: i32 = 42,
+    }
+}
+
+fn main() {
+    let mut client = ClientCvars::default();
+    let mut server = ServerCvars::default();
+    let mut third = third::Cvars::default();
+
+    client.set("cl_fov", 100.0_f32).unwrap();
+    assert_eq!(client.get::<f32>("cl_fov"), Ok(100.0));
+    server.set_str("sv_hostname", "My Server").unwrap();
+    assert_eq!(server.get::<String>("sv_hostname").unwrap(), "My Server");
+    third.set("g_int", 43).unwrap();
+    assert_eq!(third.g_int, 43);
+
+    let all: [&mut dyn SetGet; 3] = [&mut client, &mut server, &mut third];
+    let counts: Vec<_> = all.iter().map(|cvars| cvars.cvar_count()).collect();
+    assert_eq!(counts, [1, 2, 1]);
+}
@@ -0,0 +1,123 @@
+use cvars::{config, cvars, CvarError, SetGet};
+
+cvars! {
+    #![derive(Debug, Clone)]
+    #![cvars(name = RocketCvars)]
+    damage: f32 = 100.0,
+    #[cvars(min = 0, max = 100)]
+    ammo_max: i32 = 20,
+}
+
+cvars! {
+    #![derive(Debug, Clone)]
+    #![cvars(name = WeaponCvars)]
+    switch_delay: f32 = 0.5,
+    #[cvars(nested)]
+    rocket: RocketCvars = RocketCvars::default(),
+}
+
+cvars! {
+    #![derive(Debug, Clone)]
+    g_gravity: f32 = 800.0,
+    #[cvars(nested)]
+    weapons: WeaponCvars = WeaponCvars::default(),
+    g_speed: f32 = 300.0,
+}
+
+fn main() {
+    let mut cvars = Cvars::default();
+
+    assert_eq!(cvars.get_string("weapons.rocket.damage").unwrap(), "100");
+    cvars.set_str("weapons.rocket.damage", "150").unwrap();
+    assert_eq!(cvars.weapons.rocket.damage, 150.0);
+    cvars.set_str("weapons.switch_delay", "1").unwrap();
+    assert_eq!(cvars.weapons.switch_delay, 1.0);
+
+    assert_eq!(
+        cvars.get_string("weapons.rocket.bla"),
+        Err(CvarError::NotFound {
+            name: "weapons.rocket.bla".to_owned()
+        })
+    );
+    assert_eq!(
+        cvars.get_string("bla.damage"),
+        Err(CvarError::NotFound {
+            name: "bla.damage".to_owned()
+        })
+    );
+    assert!(matches!(
+        cvars.set_str("weapons.rocket.ammo_max", "lots"),
+        Err(CvarError::Parse { name, .. }) if name == "weapons.rocket.ammo_max"
+    ));
+    assert!(cvars.get_string("weapons").is_err());
+
+    // Typed getters and setters
+    assert_eq!(cvars.get::<i32>("weapons.rocket.ammo_max"), Ok(20));
+    cvars.set("weapons.rocket.ammo_max", 30).unwrap();
+    assert_eq!(cvars.weapons.rocket.ammo_max, 30);
+    assert_eq!(
+        cvars.set("weapons.rocket.ammo_max", 30.0),
+        Err(CvarError::TypeMismatch {
+            name: "weapons.rocket.ammo_max".to_owned(),
+            expected: "i32",
+            found: "f64",
+        })
+    );
+    cvars.set_numeric("weapons.rocket.ammo_max", 25.0).unwrap();
+    assert_eq!(
+        cvars.cvar_range("weapons.rocket.ammo_max"),
+        Some((0.0, 100.0))
+    );
+    assert_eq!(cvars.get_numeric::<u8>("weapons.rocket.ammo_max"), Ok(25));
+    cvars.weapons.rocket.ammo_max = 20;
+
+    assert_eq!(Cvars::CVAR_COUNT, 5);
+    assert_eq!(cvars.cvar_count(), 5);
+    let names: Vec<_> = cvars.cvar_infos().iter().map(|info| info.name).collect();
+    assert_eq!(
+        names,
+        [
+            "g_gravity",
+            "weapons.switch_delay",
+            "weapons.rocket.damage",
+            "weapons.rocket.ammo_max",
+            "g_speed",
+        ]
+    );
+    let info = cvars.cvar_info("weapons.rocket.ammo_max").unwrap();
+    assert_eq!(info.ty, "i32");
+    assert_eq!(info.default, Some("20"));
+
+    assert_eq!(cvars.default_string("weapons.rocket.damage").unwrap(), "100");
+    let changed: Vec<_> = cvars.changed().iter().map(|c| c.name).collect();
+    assert_eq!(changed, ["weapons.switch_delay", "weapons.rocket.damage"]);
+
+    cvars.reset("weapons.rocket.damage").unwrap();
+    assert_eq!(cvars.weapons.rocket.damage, 100.0);
+    cvars.weapons.rocket.ammo_max = 0;
+    cvars.reset_where(&|flags| flags.cheat);
+    assert_eq!(cvars.weapons.rocket.ammo_max, 0);
+    cvars.reset_all();
+    assert_eq!(cvars.weapons.rocket.ammo_max, 20);
+    assert_eq!(cvars.weapons.switch_delay, 0.5);
+
+    // Config files and the console use the dotted names too
+    let text = "weapons.rocket.damage 200\n";
+    config::load(&mut cvars, text, config::LoadOptions::default()).unwrap();
+    assert_eq!(cvars.weapons.rocket.damage, 200.0);
+    let options = config::SaveOptions {
+        only_changed: true,
+        ..Default::default()
+    };
+    assert_eq!(config::save(&cvars, options), text);
+
+    // Also through the trait
+    let dyn_cvars: &mut dyn SetGet = &mut cvars;
+    dyn_cvars.set_str("weapons.rocket.ammo_max", "40").unwrap();
+    assert_eq!(dyn_cvars.suggest("weapons.rocket.ammo_mx")[0], "weapons.rocket.ammo_max");
+    assert_eq!(cvars.weapons.rocket.ammo_max, 40);
+
+    *cvars.get_mut::<f32>("weapons.rocket.damage").unwrap() = 250.0;
+    assert_eq!(*cvars.get_ref::<f32>("weapons.rocket.damage").unwrap(), 250.0);
+    assert!(cvars.get_any("weapons.rocket.bla").is_none());
+}
@@ -0,0 +1,40 @@
+use cvars::cvars;
+
+cvars! {
+    #![derive(Debug, Clone)]
+    #![cvars(name = WeaponCvars)]
+    rocket_damage: f32 = 100.0,
+    rocket_speed: f32 = 500.0,
+}
+
+// The parent initializes the nested struct differently than its own Default.
+cvars! {
+    #![derive(Debug, Clone)]
+    g_gravity: f32 = 800.0,
+    #[cvars(nested)]
+    weapons: WeaponCvars = WeaponCvars {
+        rocket_damage: 200.0,
+        ..WeaponCvars::default()
+    },
+}
+
+fn main() {
+    let mut cvars = Cvars::default();
+    assert_eq!(cvars.default_string("weapons.rocket_damage").unwrap(), "200");
+
+    cvars.weapons.rocket_damage = 50.0;
+    cvars.reset("weapons.rocket_damage").unwrap();
+    assert_eq!(cvars.weapons.rocket_damage, 200.0);
+    assert!(cvars.changed().is_empty());
+
+    cvars.weapons.rocket_damage = 50.0;
+    cvars.weapons.rocket_speed = 50.0;
+    cvars.reset_all();
+    assert_eq!(cvars.weapons.rocket_damage, 200.0);
+    assert_eq!(cvars.weapons.rocket_speed, 500.0);
+    assert!(cvars.changed().is_empty());
+
+    // The nested struct on its own still uses its own default.
+    cvars.weapons.reset_all();
+    assert_eq!(cvars.weapons.rocket_damage, 100.0);
+}
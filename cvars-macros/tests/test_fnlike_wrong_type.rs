@@ -0,0 +1,18 @@
+use cvars::cvars;
+
+cvars! {
+    g_damage: f32 = 50.0,
+    #[cfg(any())]
+    g_name: String = String::new(),
+}
+
+fn main() {
+    let mut cvars = Cvars::default();
+
+    // No cvar has these types.
+    let _ = cvars.get::<f64>("g_damage");
+    let _ = cvars.set("g_damage", 50);
+
+    // The only cvar with this type is disabled.
+    let _ = cvars.get_ref::<String>("g_name");
+}
@@ -0,0 +1,259 @@
+//! Saving and loading cvars to/from Quake-style config files.
+//!
+//! Useful for example if your game has multiple balance presets.
+//!
+//! # Format
+//!
+//! Each line contains a cvar's name and its value separated by whitespace.
+//! Values containing whitespace (or other special characters) are enclosed in double quotes,
+//! inside quotes `\"`, `\\`, `\n` and `\t` are used to write a literal quote, backslash, newline or tab.
+//! Everything after `//` (outside quotes) is a comment. Empty lines are ignored.
+//!
+//! This is the same syntax as the in-game console uses (see [`tokenize`] for all the rules)
+//! so config files can also be run as console scripts.
+//!
+//! ```text
+//! // Rockets go brrr
+//! g_rocket_launcher_damage 150
+//! g_rocket_launcher_ammo_max 40 // Double the default
+//! sv_hostname "My Server"
+//! ```
+//!
+//! # Example
+//!
+//! ```rust
+//! use cvars::{config, cvars};
+//!
+//! cvars! {
+//!     g_rocket_launcher_damage: f32 = 100.0,
+//!     sv_hostname: String = "Unnamed".to_owned(),
+//! }
+//!
+//! let mut cvars = Cvars::default();
+//! cvars.sv_hostname = "My Server".to_owned();
+//! let text = config::save(&cvars, config::SaveOptions::default());
+//! assert_eq!(text, "g_rocket_launcher_damage 100\nsv_hostname \"My Server\"\n");
+//!
+//! let options = config::SaveOptions {
+//!     only_changed: true,
+//!     ..Default::default()
+//! };
+//! let text = config::save(&cvars, options);
+//! assert_eq!(text, "sv_hostname \"My Server\"\n");
+//!
+//! let mut loaded = Cvars::default();
+//! config::load(&mut loaded, &text, config::LoadOptions::default()).unwrap();
+//! assert_eq!(loaded.sv_hostname, "My Server");
+//! ```
+
+mod lexer;
+
+use std::{error::Error, fmt, fs, io, path::Path};
+
+use crate::{CvarError, SetGet};
+
+pub use lexer::{tokenize, LexError};
+
+/// Options for [`save`] and [`save_file`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SaveOptions {
+    /// Only write cvars whose values differ from their defaults.
+    ///
+    /// By default, all cvars are written.
+    pub only_changed: bool,
+
+    /// Only write cvars marked with `#[cvars(flags(archive))]`.
+    ///
+    /// By default, all cvars are written.
+    pub only_archive: bool,
+}
+
+/// Options for [`load`] and [`load_file`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct LoadOptions {
+    /// Stop at the first invalid line.
+    ///
+    /// By default, invalid lines are reported but all the valid lines are still applied.
+    pub strict: bool,
+}
+
+/// An error encountered while loading a config file.
+#[derive(Debug)]
+pub enum ConfigError {
+    /// The file could not be read.
+    Io(io::Error),
+
+    /// The line is not in the `name value` format.
+    Syntax {
+        /// Line number, starting at 1.
+        line: usize,
+        /// What went wrong.
+        message: String,
+    },
+
+    /// The line has the correct format but the cvar could not be set.
+    Cvar {
+        /// Line number, starting at 1.
+        line: usize,
+        /// The error returned by `set_str`.
+        error: CvarError,
+    },
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigError::Io(err) => write!(f, "failed to read config: {err}"),
+            ConfigError::Syntax { line, message } => write!(f, "line {line}: {message}"),
+            ConfigError::Cvar { line, error } => write!(f, "line {line}: {error}"),
+        }
+    }
+}
+
+impl Error for ConfigError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ConfigError::Io(err) => Some(err),
+            ConfigError::Syntax { .. } => None,
+            ConfigError::Cvar { error, .. } => Some(error),
+        }
+    }
+}
+
+/// Write cvars as `name value` lines.
+pub fn save(cvars: &dyn SetGet, options: SaveOptions) -> String {
+    // `changed` is in the same order as `cvar_infos` so they can be walked together.
+    let mut changed = options
+        .only_changed
+        .then(|| cvars.changed().into_iter().peekable());
+    let mut text = String::new();
+    for info in cvars.cvar_infos() {
+        let changed_value = match &mut changed {
+            Some(changed) => match changed.next_if(|changed| changed.name == info.name) {
+                Some(changed) => Some(changed.value),
+                None => continue,
+            },
+            None => None,
+        };
+        if options.only_archive && !info.flags.archive {
+            continue;
+        }
+        // The name comes from cvar_infos so it always exists.
+        let value = changed_value.unwrap_or_else(|| cvars.get_string(info.name).unwrap());
+        text.push_str(info.name);
+        text.push(' ');
+        text.push_str(&quote(&value));
+        text.push('\n');
+    }
+    text
+}
+
+/// Write cvars as `name value` lines into the file at `path`, replacing its contents.
+pub fn save_file(
+    cvars: &dyn SetGet,
+    path: impl AsRef<Path>,
+    options: SaveOptions,
+) -> io::Result<()> {
+    fs::write(path, save(cvars, options))
+}
+
+/// Parse `text` as `name value` lines and set the cvars accordingly.
+///
+/// Returns all the errors encountered, each with its line number.
+/// Unless [`LoadOptions::strict`] is set, the valid lines are applied even if some lines are invalid.
+pub fn load(
+    cvars: &mut dyn SetGet,
+    text: &str,
+    options: LoadOptions,
+) -> Result<(), Vec<ConfigError>> {
+    let mut errors = Vec::new();
+    'lines: for (index, line_text) in text.lines().enumerate() {
+        let line = index + 1;
+        let commands = match tokenize(line_text) {
+            Ok(commands) => commands,
+            Err(err) => {
+                errors.push(ConfigError::Syntax {
+                    line,
+                    message: err.to_string(),
+                });
+                if options.strict {
+                    break;
+                }
+                continue;
+            }
+        };
+        // Several commands separated by `;` can be on one line, same as in the console.
+        for words in commands {
+            let res = match &words[..] {
+                [name, value] => cvars
+                    .set_str(name, value)
+                    .map_err(|error| ConfigError::Cvar { line, error }),
+                [name] => Err(ConfigError::Syntax {
+                    line,
+                    message: format!("missing value for {name}"),
+                }),
+                [_, _, extra, ..] => Err(ConfigError::Syntax {
+                    line,
+                    message: format!("expected only cvar name and value, found {extra}"),
+                }),
+                [] => unreachable!("tokenize leaves out empty commands"),
+            };
+            if let Err(err) = res {
+                errors.push(err);
+                if options.strict {
+                    break 'lines;
+                }
+            }
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+/// Read the file at `path` and load it like [`load`].
+pub fn load_file(
+    cvars: &mut dyn SetGet,
+    path: impl AsRef<Path>,
+    options: LoadOptions,
+) -> Result<(), Vec<ConfigError>> {
+    let text = fs::read_to_string(path).map_err(|err| vec![ConfigError::Io(err)])?;
+    load(cvars, &text, options)
+}
+
+/// Quote the value if [`tokenize`] wouldn't read it back as a single word otherwise.
+///
+/// ```rust
+/// use cvars::config::{quote, tokenize};
+///
+/// assert_eq!(quote("800"), "800");
+/// assert_eq!(quote("it's; \"quoted\""), r#""it's; \"quoted\"""#);
+/// assert_eq!(tokenize(&quote("a // b"))?, [["a // b"]]);
+/// # Ok::<(), cvars::config::LexError>(())
+/// ```
+pub fn quote(value: &str) -> String {
+    let needs_quotes = value.is_empty()
+        || value.contains(|c: char| c.is_whitespace() || matches!(c, '"' | '\'' | '\\' | ';'))
+        || value.contains("//");
+    if !needs_quotes {
+        return value.to_owned();
+    }
+
+    let mut quoted = String::with_capacity(value.len() + 2);
+    quoted.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            // A literal newline would end the line so the rest would be read as another line.
+            '\n' => quoted.push_str("\\n"),
+            '\t' => quoted.push_str("\\t"),
+            _ => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
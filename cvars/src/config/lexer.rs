@@ -0,0 +1,268 @@
+use std::{error::Error, fmt, iter::Peekable, mem, str::CharIndices};
+
+/// Split console input or a line of a config file into commands and the commands into words.
+///
+/// The rules are similar to a shell:
+/// - Words are separated by whitespace.
+/// - Commands are separated by `;` or newlines.
+/// - Double quotes group words, inside them `\"`, `\\`, `\n` and `\t` are escape sequences.
+/// - Single quotes group words, everything inside them is literal.
+/// - A backslash outside quotes makes the next character literal, e.g. `\;` or `\ `.
+/// - `//` outside quotes starts a comment which lasts until the end of the line.
+///
+/// Quoted and unquoted parts next to each other form a single word, e.g. `a"b c"` is `ab c`.
+/// Empty commands are left out.
+///
+/// [`quote`](super::quote) produces words which this function reads back unchanged.
+///
+/// ```rust
+/// use cvars::config::tokenize;
+///
+/// let commands = tokenize(r#"sv_hostname "My Server"; g_gravity 800 // comment"#).unwrap();
+/// assert_eq!(commands, [vec!["sv_hostname", "My Server"], vec!["g_gravity", "800"]]);
+///
+/// let err = tokenize(r#"sv_hostname "My Server"#).unwrap_err();
+/// assert_eq!(err.column, 13);
+/// ```
+pub fn tokenize(line: &str) -> Result<Vec<Vec<String>>, LexError> {
+    let mut lexer = Lexer {
+        chars: line.char_indices().peekable(),
+        line,
+    };
+    let mut commands = Vec::new();
+    let mut words = Vec::new();
+    loop {
+        lexer.skip_whitespace();
+        match lexer.peek() {
+            None => break,
+            Some(';' | '\n') => {
+                lexer.next();
+                if !words.is_empty() {
+                    commands.push(mem::take(&mut words));
+                }
+            }
+            Some('/') if lexer.rest().starts_with("//") => {
+                // Skip the rest of the line but not the following lines.
+                while !matches!(lexer.peek(), None | Some('\n')) {
+                    lexer.next();
+                }
+            }
+            Some(_) => words.push(lexer.word()?),
+        }
+    }
+    if !words.is_empty() {
+        commands.push(words);
+    }
+    Ok(commands)
+}
+
+struct Lexer<'a> {
+    chars: Peekable<CharIndices<'a>>,
+    line: &'a str,
+}
+
+impl Lexer<'_> {
+    fn peek(&mut self) -> Option<char> {
+        self.chars.peek().map(|&(_, c)| c)
+    }
+
+    fn next(&mut self) -> Option<(usize, char)> {
+        self.chars.next()
+    }
+
+    /// The unprocessed part of the line.
+    fn rest(&mut self) -> &str {
+        match self.chars.peek() {
+            Some(&(i, _)) => &self.line[i..],
+            None => "",
+        }
+    }
+
+    /// The 1-based column of the char at byte index `i`, counted in chars.
+    fn column(&self, i: usize) -> usize {
+        let line_start = self.line[..i].rfind('\n').map_or(0, |start| start + 1);
+        self.line[line_start..i].chars().count() + 1
+    }
+
+    fn error(&self, i: usize, message: impl Into<String>) -> LexError {
+        LexError {
+            column: self.column(i),
+            message: message.into(),
+        }
+    }
+
+    fn skip_whitespace(&mut self) {
+        while matches!(self.peek(), Some(c) if c.is_whitespace() && c != '\n') {
+            self.next();
+        }
+    }
+
+    /// Read one word, handling quotes and escapes. Stops before whitespace, `;`, a comment or the end.
+    fn word(&mut self) -> Result<String, LexError> {
+        let mut word = String::new();
+        while let Some(c) = self.peek() {
+            if c.is_whitespace() || c == ';' || self.rest().starts_with("//") {
+                break;
+            }
+            let (i, c) = self.next().unwrap();
+            match c {
+                '"' => self.double_quoted(i, &mut word)?,
+                '\'' => self.single_quoted(i, &mut word)?,
+                '\\' => match self.next() {
+                    Some((_, escaped)) => word.push(escaped),
+                    None => return Err(self.error(i, "backslash at the end of the line")),
+                },
+                _ => word.push(c),
+            }
+        }
+        Ok(word)
+    }
+
+    /// Read the contents of a double quoted string, `start` is the index of the opening quote.
+    fn double_quoted(&mut self, start: usize, word: &mut String) -> Result<(), LexError> {
+        loop {
+            match self.next() {
+                Some((_, '"')) => return Ok(()),
+                Some((i, '\\')) => match self.next() {
+                    Some((_, '"')) => word.push('"'),
+                    Some((_, '\\')) => word.push('\\'),
+                    Some((_, 'n')) => word.push('\n'),
+                    Some((_, 't')) => word.push('\t'),
+                    Some((_, c)) => {
+                        return Err(self.error(i, format!("unknown escape sequence \\{c}")))
+                    }
+                    None => return Err(self.error(start, "unterminated double quote")),
+                },
+                Some((_, c)) => word.push(c),
+                None => return Err(self.error(start, "unterminated double quote")),
+            }
+        }
+    }
+
+    /// Read the contents of a single quoted string, `start` is the index of the opening quote.
+    fn single_quoted(&mut self, start: usize, word: &mut String) -> Result<(), LexError> {
+        loop {
+            match self.next() {
+                Some((_, '\'')) => return Ok(()),
+                Some((_, c)) => word.push(c),
+                None => return Err(self.error(start, "unterminated single quote")),
+            }
+        }
+    }
+}
+
+/// An error returned by [`tokenize`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LexError {
+    /// The column where the problem is, counted in chars from 1.
+    ///
+    /// For unterminated quotes, it's the column of the opening quote.
+    pub column: usize,
+    /// What went wrong.
+    pub message: String,
+}
+
+impl fmt::Display for LexError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "column {}: {}", self.column, self.message)
+    }
+}
+
+impl Error for LexError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lex_err(line: &str) -> (usize, String) {
+        let err = tokenize(line).unwrap_err();
+        (err.column, err.message)
+    }
+
+    #[test]
+    fn words_and_commands() {
+        assert_eq!(tokenize("").unwrap(), [] as [Vec<String>; 0]);
+        assert_eq!(tokenize("  a  b\tc ").unwrap(), [["a", "b", "c"]]);
+        assert_eq!(tokenize("a 1;b 2").unwrap(), [["a", "1"], ["b", "2"]]);
+        assert_eq!(tokenize("a;;b; ;").unwrap(), [["a"], ["b"]]);
+        assert_eq!(
+            tokenize("a 1\nb 2\n\nc").unwrap(),
+            [vec!["a", "1"], vec!["b", "2"], vec!["c"]]
+        );
+    }
+
+    #[test]
+    fn double_quotes() {
+        assert_eq!(
+            tokenize(r#"a "b c" "" d"#).unwrap(),
+            [["a", "b c", "", "d"]]
+        );
+        assert_eq!(tokenize(r#""a;b // c""#).unwrap(), [["a;b // c"]]);
+        assert_eq!(tokenize(r#"a"b c"d"#).unwrap(), [["ab cd"]]);
+        assert_eq!(tokenize(r#""\" \\ \n \t""#).unwrap(), [["\" \\ \n \t"]]);
+    }
+
+    #[test]
+    fn single_quotes() {
+        assert_eq!(tokenize("a 'b c'").unwrap(), [["a", "b c"]]);
+        // Everything is literal, including backslashes and double quotes.
+        assert_eq!(tokenize(r#"'\n "x" \'"#).unwrap(), [[r#"\n "x" \"#]]);
+        assert_eq!(
+            tokenize(r#""it's" 'say "hi"'"#).unwrap(),
+            [["it's", "say \"hi\""]]
+        );
+    }
+
+    #[test]
+    fn backslash_outside_quotes() {
+        assert_eq!(tokenize(r"a\ b c\;d").unwrap(), [["a b", "c;d"]]);
+        assert_eq!(tokenize(r#"\"a\' \\ \n"#).unwrap(), [["\"a'", "\\", "n"]]);
+        assert_eq!(tokenize(r"\/\/a").unwrap(), [["//a"]]);
+    }
+
+    #[test]
+    fn comments() {
+        assert_eq!(tokenize("// a b").unwrap(), [] as [Vec<String>; 0]);
+        assert_eq!(tokenize("a 1 // b; c 2").unwrap(), [["a", "1"]]);
+        assert_eq!(tokenize("a 1// b").unwrap(), [["a", "1"]]);
+        assert_eq!(
+            tokenize("a // b\nc 2").unwrap(),
+            [vec!["a"], vec!["c", "2"]]
+        );
+        assert_eq!(tokenize("a / b").unwrap(), [["a", "/", "b"]]);
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(
+            lex_err(r#"a "b"#),
+            (3, "unterminated double quote".to_owned())
+        );
+        assert_eq!(
+            lex_err(r#"a "b\"#),
+            (3, "unterminated double quote".to_owned())
+        );
+        assert_eq!(lex_err("a 'b"), (3, "unterminated single quote".to_owned()));
+        assert_eq!(
+            lex_err(r"a\"),
+            (2, "backslash at the end of the line".to_owned())
+        );
+        assert_eq!(
+            lex_err(r#"x "ab\q""#),
+            (6, r"unknown escape sequence \q".to_owned())
+        );
+        assert_eq!(
+            tokenize(r#"x "\q""#).unwrap_err().to_string(),
+            r"column 4: unknown escape sequence \q"
+        );
+    }
+
+    #[test]
+    fn error_columns() {
+        // Columns are counted in chars, not bytes.
+        assert_eq!(lex_err(r#"ěšč "\q""#).0, 6);
+        assert_eq!(lex_err("ěšč 'x").0, 5);
+        // And from the start of the current line.
+        assert_eq!(lex_err("a 1\nb \"x").0, 3);
+    }
+}
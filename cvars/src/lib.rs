@@ -80,6 +80,7 @@
 //! - A struct containing all your cvars
 //! - A Default impl with the specified default values
 //! - Methods to set and get cvars by name
+//! - A table of information about each cvar (name, type, default value, doc comment)
 //! - Some utility methods
 //!
 //! The most important methods have these signatures (same as on the `SetGet` trait):
@@ -90,13 +91,13 @@
 //!     /// Finds the cvar whose name matches `cvar_name` and returns its value as a `String`.
 //!     ///
 //!     /// Returns `Err` if the cvar doesn't exist.
-//!     pub fn get_string(&self, cvar_name: &str) -> Result<String, String>
+//!     pub fn get_string(&self, cvar_name: &str) -> Result<String, cvars::CvarError>
 //!     # { unimplemented!() }
 //!
 //!     /// Finds the cvar whose name matches `cvar_name`, tries to parse `str_value` to its type and sets it to the parsed value.
 //!     ///
 //!     /// Returns `Err` if the cvar doesn't exist or if `str_value` fails to parse to its type.
-//!     pub fn set_str(&mut self, cvar_name: &str, str_value: &str) -> Result<(), String>
+//!     pub fn set_str(&mut self, cvar_name: &str, str_value: &str) -> Result<(), cvars::CvarError>
 //!     # { unimplemented!() }
 //! }
 //! ```
@@ -131,11 +132,107 @@
 //!
 //! - **Skipping fields** - If a field is not meant to be configurable, mark it with `#[cvars(skip)]`.
 //!
-//! - **Checking cvars are sorted** - To make the macro panic if it detects that the cvars are not sorted,
-//!   use the `#[cvars(sorted)]` attribute on the struct.
+//! - **Checking cvars are sorted** - To make the macro emit a compile error
+//!   if it detects that the cvars are not sorted, use the `#[cvars(sorted)]` attribute on the struct.
 //!   Note if you're using the `cvars` macro instead of deriving `SetGet`,
 //!   you have to use it as an an inner attribute.
 //!
+//! - **Lookup table** - By default, the generated methods find cvars by matching on their names
+//!   which compiles to one string comparison per cvar. For structs with thousands of cvars,
+//!   use `#[cvars(lookup_table)]` on the struct to find them by binary search in a sorted table instead.
+//!   It's faster at runtime and generates less code so it also improves compile times.
+//!   Just like `sorted`, it has to be an inner attribute with the `cvars` macro.
+//!
+//! - **Name enum** - Use `#[cvars(name_enum)]` on the struct to also generate an enum called `CvarName`
+//!   with one variant per cvar (`g_rocket_damage` becomes `CvarName::GRocketDamage`)
+//!   so that code which handles cvars one by one can use exhaustive matching.
+//!   It impls `FromStr` and `Display` and has `as_str` and an `ALL` constant with all the variants.
+//!   The generated methods then parse names into the enum before matching on it.
+//!   Use `#[cvars(name_enum = SomeName)]` to pick a different name, e.g. when there are multiple cvar structs
+//!   in one module. Nested cvars are not included, their struct can have its own enum.
+//!
+//!   ```rust
+//!   use cvars::cvars;
+//!
+//!   cvars! {
+//!       #![cvars(name_enum)]
+//!       g_rocket_damage: f32 = 100.0,
+//!       sv_hostname: String = "Unnamed".to_owned(),
+//!   }
+//!
+//!   fn is_replicated(name: CvarName) -> bool {
+//!       match name {
+//!           CvarName::GRocketDamage => true,
+//!           CvarName::SvHostname => false,
+//!       }
+//!   }
+//!
+//!   let name: CvarName = "g_rocket_damage".parse().unwrap();
+//!   assert!(is_replicated(name));
+//!   assert_eq!(CvarName::ALL.len(), 2);
+//!   assert_eq!(CvarName::SvHostname.as_str(), "sv_hostname");
+//!   ```
+//!
+//! - **Constraints** - To reject invalid values, use `#[cvars(min = ..., max = ...)]`,
+//!   `#[cvars(one_of = [...])]` or `#[cvars(validate = path::to_fn)]` on the field.
+//!   The validation function takes a reference to the new value and returns `Result<(), String>`.
+//!   The generated `set` and `set_str` return [`CvarError::Invalid`] instead of storing such values.
+//!   The constraints are also available in [`CvarInfo`] so UIs can for example build sliders.
+//!
+//!   ```rust
+//!   use cvars::cvars;
+//!
+//!   cvars! {
+//!       #[cvars(min = 0.0, max = 10.0)]
+//!       g_respawn_delay: f32 = 2.0,
+//!       #[cvars(one_of = ["low", "medium", "high"])]
+//!       r_quality: String = "high".to_owned(),
+//!       #[cvars(validate = validate_fov)]
+//!       cl_fov: f32 = 90.0,
+//!   }
+//!
+//!   fn validate_fov(fov: &f32) -> Result<(), String> {
+//!       if (60.0..=120.0).contains(fov) {
+//!           Ok(())
+//!       } else {
+//!           Err("FOV has to be between 60 and 120".to_owned())
+//!       }
+//!   }
+//!
+//!   let mut cvars = Cvars::default();
+//!   assert!(cvars.set_str("g_respawn_delay", "-5").is_err());
+//!   assert!(cvars.set_str("r_quality", "ultra").is_err());
+//!   assert!(cvars.set_str("cl_fov", "9000").is_err());
+//!   assert_eq!(cvars.cvar_info("g_respawn_delay").unwrap().max, Some("10.0"));
+//!   ```
+//!
+//! - **Nested structs** - To group cvars, put them in a separate struct which also derives `SetGet`
+//!   and mark the field containing it with `#[cvars(nested)]`.
+//!   The nested cvars are accessed by dotted names such as `weapons.rocket_damage`
+//!   and are included in `cvar_count`, `cvar_infos` and so on.
+//!   Game code accesses them as normal fields: `cvars.weapons.rocket_damage`.
+//!
+//! - **Flags** - Use `#[cvars(flags(cheat, read_only, archive, replicated, user_info))]`
+//!   on the field to tag cvars, see [`CvarFlags`].
+//!
+//! - **Change notifications** - To get notified when cvars change, add a field of type [`Observers`]
+//!   marked with `#[cvars(observers)]` and register callbacks on it.
+//!   They're called by `set`, `set_str`, `reset` and `reset_all` (and therefore also by the consoles
+//!   and when loading config files) with the cvar's name and its old and new values.
+//!
+//! - **Serde** - To generate `Serialize` and `Deserialize` impls, enable the `serde` feature
+//!   and use the `#[cvars(serde)]` attribute on the struct.
+//!   The types of all cvars (and nested cvar structs) have to impl `Serialize` and `Deserialize`.
+//!   The cvars are (de)serialized as a map from their names to values so you can use any format
+//!   supported by serde (TOML, JSON, RON, ...). Cvars missing from the input keep their default values
+//!   (with `#[derive(SetGet)]`, the struct has to impl `Default`), skipped fields are ignored.
+//!   Deserializing checks constraints such as `min` and `max` and fails if a value doesn't satisfy them.
+//!
+//! # Config files
+//!
+//! Cvars can be saved to and loaded from Quake-style config files
+//! containing `name value` lines - see the [`config`] module.
+//!
 //! # Related crates
 //!
 //! See the [README](https://github.com/martin-t/cvars) for more information about the cvars family of crates
@@ -143,7 +240,27 @@
 
 #![warn(missing_docs)]
 
+pub mod config;
+mod numeric;
+mod observers;
+mod suggest;
+
+use std::{any::Any, error::Error, fmt, hash::Hash, marker::PhantomData};
+
 pub use cvars_macros::{cvars, SetGet, SetGetDummy};
+pub use numeric::Numeric;
+pub use observers::{Observer, Observers};
+
+// Used by the generated code.
+#[doc(hidden)]
+pub use numeric::{
+    get_numeric as __get_numeric, set_numeric as __set_numeric, to_f64 as __to_f64, Number,
+};
+
+// Reexported so the generated code doesn't require users to depend on serde directly.
+#[cfg(feature = "serde")]
+#[doc(hidden)]
+pub use serde as __serde;
 
 /// A trait for writing generic code that can access cvars but doesn't know the concrete Cvars struct.
 ///
@@ -151,20 +268,466 @@ pub use cvars_macros::{cvars, SetGet, SetGetDummy};
 ///
 /// The methods provided here call those implemented directly on the concrete Cvars struct,
 /// there is no difference between them.
+/// The only exception is [`suggest`](SetGet::suggest) which only exists on the trait.
 ///
 /// Implementation note: This trait can't include the `get` and `set` methods
-/// because it would no longer be object-safe.
+/// because it would no longer be object-safe. Use `get_any` and `get_any_mut` instead.
 pub trait SetGet {
     /// Finds the cvar whose name matches `cvar_name` and returns it's value as a `String`.
     ///
     /// Returns `Err` if the cvar doesn't exist.
-    fn get_string(&self, cvar_name: &str) -> Result<String, String>;
+    fn get_string(&self, cvar_name: &str) -> Result<String, CvarError>;
 
     /// Finds the cvar whose name matches `cvar_name`, tries to parse `str_value` to its type and sets it to the parsed value.
     ///
     /// Returns `Err` if the cvar doesn't exist or if `str_value` fails to parse to its type.
-    fn set_str(&mut self, cvar_name: &str, str_value: &str) -> Result<(), String>;
+    fn set_str(&mut self, cvar_name: &str, str_value: &str) -> Result<(), CvarError>;
+
+    /// Finds the cvar whose name matches `cvar_name` and returns a reference to it as `dyn Any`.
+    ///
+    /// Downcast it to the cvar's type to get the value without going through strings.
+    ///
+    /// Returns `None` if the cvar doesn't exist.
+    fn get_any(&self, cvar_name: &str) -> Option<&dyn Any>;
+
+    /// Finds the cvar whose name matches `cvar_name` and returns a mutable reference to it as `dyn Any`.
+    ///
+    /// Changing the value this way is the same as changing the field directly,
+    /// constraints are not checked and observers are not called.
+    ///
+    /// Returns `None` if the cvar doesn't exist.
+    fn get_any_mut(&mut self, cvar_name: &str) -> Option<&mut dyn Any>;
+
+    /// Finds the cvar whose name matches `cvar_name` and resets it to its default value.
+    ///
+    /// Returns `Err` if the cvar doesn't exist.
+    fn reset(&mut self, cvar_name: &str) -> Result<(), CvarError>;
+
+    /// Resets all cvars to their default values.
+    fn reset_all(&mut self);
+
+    /// Resets cvars whose flags match `filter` to their default values.
+    ///
+    /// The default values are created only once
+    /// so this is faster than calling [`reset`](SetGet::reset) for each cvar.
+    ///
+    /// ```rust
+    /// use cvars::{cvars, SetGet};
+    ///
+    /// cvars! {
+    ///     g_armor: i32 = 100,
+    ///     #[cvars(flags(cheat))]
+    ///     g_gravity: f32 = 800.0,
+    /// }
+    ///
+    /// let mut cvars = Cvars { g_armor: 50, g_gravity: 100.0 };
+    /// cvars.reset_where(&|flags| !flags.cheat);
+    /// assert_eq!(cvars.g_armor, 100);
+    /// assert_eq!(cvars.g_gravity, 100.0);
+    /// ```
+    fn reset_where(&mut self, filter: &dyn Fn(CvarFlags) -> bool);
+
+    /// Finds the cvar whose name matches `cvar_name` and returns its default value as a `String`.
+    ///
+    /// Returns `Err` if the cvar doesn't exist.
+    fn default_string(&self, cvar_name: &str) -> Result<String, CvarError>;
+
+    /// Returns the cvars whose values differ from their defaults, in the same order as in the struct.
+    ///
+    /// The values are compared as strings using their `Display` impls.
+    fn changed(&self) -> Vec<ChangedCvar>;
 
     /// Returns the number of cvars.
     fn cvar_count(&self) -> usize;
+
+    /// Returns information about all cvars such as their names, types and doc comments.
+    ///
+    /// The cvars are in the same order as in the struct.
+    fn cvar_infos(&self) -> &'static [CvarInfo];
+
+    /// Finds the cvar whose name matches `cvar_name` and returns information about it.
+    ///
+    /// Returns `None` if the cvar doesn't exist.
+    fn cvar_info(&self, cvar_name: &str) -> Option<&'static CvarInfo>;
+
+    /// Returns names of existing cvars similar to `cvar_name`, the most similar first.
+    ///
+    /// Useful for "did you mean" messages when a cvar is not found.
+    /// The names are compared case-insensitively by edit distance and shared prefix.
+    /// At most 5 names are returned, the list is empty if nothing is similar enough.
+    fn suggest(&self, cvar_name: &str) -> Vec<&'static str> {
+        suggest::suggest(self.cvar_infos(), cvar_name)
+    }
+
+    /// Finds the cvar whose name matches `cvar_name` and returns its flags.
+    ///
+    /// Returns `None` if the cvar doesn't exist.
+    fn cvar_flags(&self, cvar_name: &str) -> Option<CvarFlags>;
+
+    /// Finds the cvar whose name matches `cvar_name` and returns the values
+    /// from `#[cvars(one_of = [...])]` formatted using `Display`.
+    ///
+    /// Unlike [`CvarInfo::one_of`], these are the values as `set_str` expects them,
+    /// not as they're written in the source code (e.g. `Vertical` instead of `Split::Vertical`).
+    ///
+    /// Returns an empty `Vec` if any value is allowed or `Err` if the cvar doesn't exist.
+    fn allowed_values(&self, cvar_name: &str) -> Result<Vec<String>, CvarError>;
+
+    /// Finds the cvar whose name matches `cvar_name` and returns the bounds
+    /// from `#[cvars(min = ..., max = ...)]` converted to `f64`.
+    ///
+    /// Unlike [`CvarInfo::min`] and [`CvarInfo::max`], these are the evaluated values
+    /// so e.g. `MAX_FOV` becomes the constant's value.
+    /// A missing bound is returned as infinity.
+    ///
+    /// Returns `None` if the cvar doesn't exist, isn't a primitive number or has no bounds.
+    fn cvar_range(&self, cvar_name: &str) -> Option<(f64, f64)>;
+
+    /// Returns the observers if the struct has a field marked with `#[cvars(observers)]`.
+    fn observers(&self) -> Option<&Observers>;
+
+    /// Returns the observers if the struct has a field marked with `#[cvars(observers)]`.
+    ///
+    /// Use this to register observers through `dyn SetGet`.
+    fn observers_mut(&mut self) -> Option<&mut Observers>;
+}
+
+/// Information about a cvar which is known at compile time.
+///
+/// A table of these is generated for each struct by both `#[derive(SetGet)]` and `cvars! {}`.
+/// It allows tools (listing cvars, autocompletion, generating docs, ...)
+/// to find out which cvars exist without knowing the concrete Cvars struct.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CvarInfo {
+    /// The cvar's name - the same as the name of the struct field.
+    pub name: &'static str,
+
+    /// The cvar's type as written in the source code.
+    ///
+    /// Use [`type_name`](CvarInfo::type_name) to get the full path,
+    /// the same as in [`CvarError::TypeMismatch`] and [`CvarError::Parse`].
+    pub ty: &'static str,
+
+    /// See [`type_name`](CvarInfo::type_name).
+    #[doc(hidden)]
+    pub __type_name: TypeNameFn,
+
+    /// The expression used as the default value in `cvars! {}` as written in the source code.
+    ///
+    /// This is `None` for `#[derive(SetGet)]` because the default values are specified separately.
+    pub default: Option<&'static str>,
+
+    /// The smallest allowed value from `#[cvars(min = ...)]` as written in the source code.
+    ///
+    /// Meant for display, use [`SetGet::cvar_range`] to get the evaluated value.
+    pub min: Option<&'static str>,
+
+    /// The largest allowed value from `#[cvars(max = ...)]` as written in the source code.
+    ///
+    /// Meant for display, use [`SetGet::cvar_range`] to get the evaluated value.
+    pub max: Option<&'static str>,
+
+    /// The allowed values from `#[cvars(one_of = [...])]` as written in the source code.
+    ///
+    /// Empty if any value is allowed.
+    pub one_of: &'static [&'static str],
+
+    /// The validation function from `#[cvars(validate = ...)]` as written in the source code.
+    pub validate: Option<&'static str>,
+
+    /// Flags from `#[cvars(flags(...))]`.
+    pub flags: CvarFlags,
+
+    /// The cvar's doc comment, empty if it has none.
+    pub doc: &'static str,
+}
+
+/// Flags which describe how a cvar should be treated, set using `#[cvars(flags(...))]`.
+///
+/// Cvars themselves don't enforce them, it's up to the code accessing cvars dynamically.
+/// For example, the consoles refuse to change `cheat` cvars unless cheats are enabled
+/// and [`config::SaveOptions::only_archive`] saves only `archive` cvars.
+///
+/// ```rust
+/// use cvars::cvars;
+///
+/// cvars! {
+///     #[cvars(flags(cheat))]
+///     g_gravity: f32 = 800.0,
+///     #[cvars(flags(archive, user_info))]
+///     cl_name: String = "Player".to_owned(),
+/// }
+///
+/// let cvars = Cvars::default();
+/// assert!(cvars.cvar_flags("g_gravity").unwrap().cheat);
+/// assert!(cvars.cvar_flags("cl_name").unwrap().archive);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct CvarFlags {
+    /// The cvar can only be changed when cheats are enabled.
+    pub cheat: bool,
+    /// The cvar can't be changed by the user, only by code.
+    pub read_only: bool,
+    /// The cvar should be saved to the user's config.
+    pub archive: bool,
+    /// The server sends the cvar's value to clients.
+    pub replicated: bool,
+    /// The client sends the cvar's value to the server (e.g. player name).
+    pub user_info: bool,
+}
+
+impl CvarFlags {
+    /// No flags set.
+    pub const NONE: CvarFlags = CvarFlags {
+        cheat: false,
+        read_only: false,
+        archive: false,
+        replicated: false,
+        user_info: false,
+    };
+}
+
+impl CvarInfo {
+    /// The cvar's type as returned by [`std::any::type_name`], e.g. `alloc::string::String` for `String`.
+    pub fn type_name(&self) -> &'static str {
+        (self.__type_name.0)()
+    }
+
+    /// Used by the generated code to build infos of nested cvars.
+    ///
+    /// The prefixed name is leaked but this only happens once per cvar
+    /// because the result is cached for the whole program.
+    #[doc(hidden)]
+    pub fn prefixed(&self, prefix: &str) -> CvarInfo {
+        let name = format!("{prefix}.{}", self.name);
+        CvarInfo {
+            name: Box::leak(name.into_boxed_str()),
+            ..*self
+        }
+    }
+}
+
+/// Returns `std::any::type_name` of a cvar's type.
+///
+/// A function because `type_name` is not `const` and the infos are.
+/// Compared by the returned names because function pointers can't be compared reliably.
+#[doc(hidden)]
+#[derive(Clone, Copy)]
+pub struct TypeNameFn(pub fn() -> &'static str);
+
+impl PartialEq for TypeNameFn {
+    fn eq(&self, other: &Self) -> bool {
+        (self.0)() == (other.0)()
+    }
+}
+
+impl Eq for TypeNameFn {}
+
+impl fmt::Debug for TypeNameFn {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("TypeNameFn").field(&(self.0)()).finish()
+    }
+}
+
+/// A cvar whose value differs from its default, returned by [`SetGet::changed`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ChangedCvar {
+    /// The cvar's name.
+    pub name: &'static str,
+    /// The cvar's current value.
+    pub value: String,
+    /// The cvar's default value.
+    pub default: String,
+}
+
+/// Implemented for each type which at least one cvar in the struct `S` has.
+///
+/// Both `#[derive(SetGet)]` and `cvars! {}` generate the impls. The typed `get`, `get_ref`,
+/// `get_mut` and `set` require it so that asking for a type which no cvar has fails to compile.
+/// Using a type which some other cvar has is still only detected at runtime
+/// and reported as [`CvarError::TypeMismatch`].
+///
+/// Types are compared as written so cvars whose types are spelled differently
+/// but are the same type (e.g. `String` and `std::string::String` or a type alias)
+/// cause conflicting impls.
+///
+/// The check is skipped (anything which impls `Any` is accepted) for structs with nested cvars
+/// because the macro can't see the types inside the nested structs
+/// and for structs whose cvar types use the struct's generic parameters
+/// because the impl for e.g. `T` would overlap with the impl for `f32`.
+///
+/// ```rust,compile_fail
+/// use cvars::cvars;
+///
+/// cvars! {
+///     g_damage: f32 = 50.0,
+/// }
+///
+/// let cvars = Cvars::default();
+/// cvars.get::<String>("g_damage");
+/// ```
+#[diagnostic::on_unimplemented(message = "no cvar in `{S}` has type `{Self}`")]
+pub trait CvarType<S> {}
+
+/// A typed name of a cvar.
+///
+/// Both `#[derive(SetGet)]` and `cvars! {}` generate one for each cvar as an associated constant
+/// whose name is the cvar's name in uppercase, e.g. `Cvars::G_DAMAGE` for `g_damage`.
+/// Use it with `get_key`, `set_key` and `set_str_key` instead of `get`, `set` and `set_str`
+/// so that typos in names become compile errors and the type of the value is inferred from the key.
+///
+/// Keys of nested cvars are generated on the nested struct and are used with it directly,
+/// e.g. `cvars.weapons.get_key(WeaponCvars::ROCKET_DAMAGE)`.
+///
+/// # Example
+///
+/// ```rust
+/// use cvars::cvars;
+///
+/// cvars! {
+///     g_damage: f32 = 50.0,
+///     g_ammo_max: usize = 20,
+/// }
+///
+/// let mut cvars = Cvars::default();
+/// cvars.set_key(Cvars::G_AMMO_MAX, 40).unwrap();
+/// assert_eq!(cvars.get_key(Cvars::G_AMMO_MAX).unwrap(), 40);
+/// assert_eq!(Cvars::G_DAMAGE.name(), "g_damage");
+/// ```
+pub struct CvarKey<T> {
+    name: &'static str,
+    ty: PhantomData<fn() -> T>,
+}
+
+impl<T> CvarKey<T> {
+    /// Used by the generated code, the name has to belong to a cvar of type `T`.
+    #[doc(hidden)]
+    pub const fn new(name: &'static str) -> Self {
+        CvarKey {
+            name,
+            ty: PhantomData,
+        }
+    }
+
+    /// The cvar's name.
+    pub const fn name(&self) -> &'static str {
+        self.name
+    }
+}
+
+// Implemented manually because derives would require `T` to impl the traits too.
+
+impl<T> Clone for CvarKey<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for CvarKey<T> {}
+
+impl<T> PartialEq for CvarKey<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name
+    }
+}
+
+impl<T> Eq for CvarKey<T> {}
+
+impl<T> Hash for CvarKey<T> {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.name.hash(state);
+    }
+}
+
+impl<T> fmt::Debug for CvarKey<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("CvarKey").field(&self.name).finish()
+    }
 }
+
+/// An error returned when getting or setting a cvar fails.
+///
+/// Types in the errors are formatted using [`std::any::type_name`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum CvarError {
+    /// No cvar with the given name exists.
+    NotFound {
+        /// The name that was looked up.
+        name: String,
+    },
+
+    /// The cvar exists but its type is different from the one requested by the typed API (`get`, `set`).
+    TypeMismatch {
+        /// The cvar's name.
+        name: String,
+        /// The cvar's actual type.
+        expected: &'static str,
+        /// The type that was requested.
+        found: &'static str,
+    },
+
+    /// The value could not be parsed into the cvar's type.
+    Parse {
+        /// The cvar's name.
+        name: String,
+        /// The value that failed to parse.
+        value: String,
+        /// The cvar's type.
+        ty: &'static str,
+        /// The error message produced by the type's `FromStr` impl.
+        source_message: String,
+    },
+
+    /// The value was parsed successfully but it's not allowed by the cvar's constraints
+    /// (`min`, `max`, `one_of` or `validate`).
+    Invalid {
+        /// The cvar's name.
+        name: String,
+        /// The rejected value.
+        value: String,
+        /// Why the value was rejected.
+        message: String,
+    },
+}
+
+impl CvarError {
+    /// Used by the generated code to turn a cvar name relative to a nested struct into the full name.
+    #[doc(hidden)]
+    pub fn prefixed(mut self, prefix: &str) -> Self {
+        let name = match &mut self {
+            CvarError::NotFound { name } => name,
+            CvarError::TypeMismatch { name, .. } => name,
+            CvarError::Parse { name, .. } => name,
+            CvarError::Invalid { name, .. } => name,
+        };
+        *name = format!("{prefix}.{name}");
+        self
+    }
+}
+
+impl fmt::Display for CvarError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CvarError::NotFound { name } => write!(f, "Cvar named {name} not found"),
+            CvarError::TypeMismatch {
+                name,
+                expected,
+                found,
+            } => write!(f, "Cvar named {name} has type {expected}, not {found}"),
+            CvarError::Parse {
+                value,
+                ty,
+                source_message,
+                ..
+            } => write!(f, "failed to parse {value} as type {ty}: {source_message}"),
+            CvarError::Invalid {
+                name,
+                value,
+                message,
+            } => write!(f, "invalid value {value} for cvar {name}: {message}"),
+        }
+    }
+}
+
+impl Error for CvarError {}
@@ -0,0 +1,247 @@
+use std::{any::Any, fmt};
+
+use crate::{CvarError, SetGet};
+
+/// A primitive number type which can be converted to and from the numeric type of a cvar.
+///
+/// Implemented for all integer and floating point types.
+/// Used by the generated `set_numeric` and `get_numeric` methods.
+///
+/// The conversion succeeds only if the value fits into the target type:
+/// - Integers must be in range of the target integer type.
+/// - Integers converted to floats must be exactly representable by the target float type.
+/// - Floats converted to integers must be finite whole numbers in range of the target type.
+/// - `f64` converted to `f32` is rounded to the nearest `f32` but must not overflow to infinity.
+///
+/// # Example
+///
+/// ```rust
+/// use cvars::{cvars, CvarError};
+///
+/// cvars! {
+///     cl_fps_max: usize = 144,
+///     g_speed: f32 = 300.0,
+/// }
+///
+/// let mut cvars = Cvars::default();
+///
+/// // `cvars.set("cl_fps_max", 60)` would fail because `60` is an `i32`.
+/// cvars.set_numeric("cl_fps_max", 60).unwrap();
+/// cvars.set_numeric("cl_fps_max", 75.0).unwrap();
+/// assert_eq!(cvars.cl_fps_max, 75);
+/// assert_eq!(cvars.get_numeric::<f64>("g_speed"), Ok(300.0));
+///
+/// assert!(matches!(
+///     cvars.set_numeric("cl_fps_max", -1),
+///     Err(CvarError::Invalid { message, .. }) if message == "out of range for usize"
+/// ));
+/// ```
+pub trait Numeric: Copy + fmt::Display + Any + private::Sealed {
+    #[doc(hidden)]
+    fn to_number(self) -> Number;
+
+    #[doc(hidden)]
+    fn from_number(number: Number) -> Result<Self, String>;
+}
+
+/// Any primitive number, wide enough to hold all of them without loss.
+#[doc(hidden)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Number {
+    Signed(i128),
+    Unsigned(u128),
+    Float(f64),
+}
+
+impl fmt::Display for Number {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Number::Signed(value) => write!(f, "{value}"),
+            Number::Unsigned(value) => write!(f, "{value}"),
+            Number::Float(value) => write!(f, "{value}"),
+        }
+    }
+}
+
+mod private {
+    pub trait Sealed {}
+}
+
+macro_rules! impl_numeric_int {
+    ($variant:ident as $wide:ty: $($ty:ty),*) => {
+        $(
+            impl private::Sealed for $ty {}
+
+            impl Numeric for $ty {
+                fn to_number(self) -> Number {
+                    // Lossless, no primitive integer is wider than 128 bits.
+                    Number::$variant(self as $wide)
+                }
+
+                fn from_number(number: Number) -> Result<Self, String> {
+                    let converted = match number {
+                        Number::Signed(value) => Self::try_from(value).ok(),
+                        Number::Unsigned(value) => Self::try_from(value).ok(),
+                        Number::Float(value) => {
+                            if !value.is_finite() || value.fract() != 0.0 {
+                                return Err(format!("not a whole number, expected {}", stringify!($ty)));
+                            }
+                            // Check the range first because float to int casts saturate.
+                            if value >= Self::MIN as f64 && value < 2.0_f64.powi(128) {
+                                if value < 0.0 {
+                                    Self::try_from(value as i128).ok()
+                                } else {
+                                    Self::try_from(value as u128).ok()
+                                }
+                            } else {
+                                None
+                            }
+                        }
+                    };
+                    converted.ok_or_else(|| format!("out of range for {}", stringify!($ty)))
+                }
+            }
+        )*
+    };
+}
+
+impl_numeric_int!(Signed as i128: i8, i16, i32, i64, i128, isize);
+impl_numeric_int!(Unsigned as u128: u8, u16, u32, u64, u128, usize);
+
+macro_rules! impl_numeric_float {
+    ($($ty:ident),*) => {
+        $(
+            impl private::Sealed for $ty {}
+
+            impl Numeric for $ty {
+                fn to_number(self) -> Number {
+                    Number::Float(self.into())
+                }
+
+                fn from_number(number: Number) -> Result<Self, String> {
+                    // Values that don't survive the round trip aren't exactly representable.
+                    // The MAX values are excluded because they round up to a power of two
+                    // which then saturates back to MAX when cast to an integer.
+                    match number {
+                        Number::Signed(value) if value != i128::MAX && value as $ty as i128 == value => {
+                            Ok(value as $ty)
+                        }
+                        Number::Unsigned(value) if value != u128::MAX && value as $ty as u128 == value => {
+                            Ok(value as $ty)
+                        }
+                        Number::Signed(_) | Number::Unsigned(_) => {
+                            Err(format!("can't be represented exactly as {}", stringify!($ty)))
+                        }
+                        Number::Float(value) => {
+                            let converted = value as $ty;
+                            if converted.is_infinite() && value.is_finite() {
+                                Err(format!("out of range for {}", stringify!($ty)))
+                            } else {
+                                Ok(converted)
+                            }
+                        }
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_numeric_float!(f32, f64);
+
+/// Calls `$body` with `$ty` set to the concrete numeric type of `$cvar` or returns `$default`.
+macro_rules! with_numeric_type {
+    ($cvar:expr, $ty:ident => $body:expr, $default:expr) => {
+        with_numeric_type!(@ $cvar, $ty => $body, $default;
+            i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize, f32, f64)
+    };
+    (@ $cvar:expr, $ty:ident => $body:expr, $default:expr; $($t:ident),*) => {
+        $(
+            if $cvar.is::<$t>() {
+                type $ty = $t;
+                $body
+            } else
+        )* {
+            $default
+        }
+    };
+}
+
+/// Used by the generated `set_numeric`.
+#[doc(hidden)]
+pub fn set_numeric<N: Numeric>(
+    cvars: &mut dyn SetGet,
+    cvar_name: &str,
+    value: N,
+) -> Result<(), CvarError> {
+    let cvar = cvars
+        .get_any(cvar_name)
+        .ok_or_else(|| not_found(cvar_name))?;
+    let number = value.to_number();
+    // Setting through `set_str` runs the cvar's constraints and observers.
+    // This is lossless because `Display` for numbers prints enough digits to round trip.
+    let str_value = with_numeric_type!(
+        cvar,
+        T => T::from_number(number).map(|value| value.to_string()),
+        return Err(type_mismatch::<N>(cvars, cvar_name))
+    );
+    let str_value = str_value.map_err(|message| CvarError::Invalid {
+        name: cvar_name.to_owned(),
+        value: number.to_string(),
+        message,
+    })?;
+    cvars.set_str(cvar_name, &str_value)
+}
+
+/// Used by the generated `get_numeric`.
+#[doc(hidden)]
+pub fn get_numeric<N: Numeric>(cvars: &dyn SetGet, cvar_name: &str) -> Result<N, CvarError> {
+    let cvar = cvars
+        .get_any(cvar_name)
+        .ok_or_else(|| not_found(cvar_name))?;
+    let number = with_numeric_type!(
+        cvar,
+        T => cvar.downcast_ref::<T>().unwrap().to_number(),
+        return Err(type_mismatch::<N>(cvars, cvar_name))
+    );
+    N::from_number(number).map_err(|message| CvarError::Invalid {
+        name: cvar_name.to_owned(),
+        value: number.to_string(),
+        message,
+    })
+}
+
+/// Used by the generated `cvar_range`.
+///
+/// Returns `None` if `value` isn't a primitive number.
+#[doc(hidden)]
+pub fn to_f64(value: &dyn Any) -> Option<f64> {
+    let number = with_numeric_type!(
+        value,
+        T => value.downcast_ref::<T>().unwrap().to_number(),
+        return None
+    );
+    // Lossy for large integers but bounds are only used for display and UI such as sliders.
+    let value = match number {
+        Number::Signed(value) => value as f64,
+        Number::Unsigned(value) => value as f64,
+        Number::Float(value) => value,
+    };
+    Some(value)
+}
+
+fn not_found(cvar_name: &str) -> CvarError {
+    CvarError::NotFound {
+        name: cvar_name.to_owned(),
+    }
+}
+
+fn type_mismatch<N>(cvars: &dyn SetGet, cvar_name: &str) -> CvarError {
+    CvarError::TypeMismatch {
+        name: cvar_name.to_owned(),
+        expected: cvars
+            .cvar_info(cvar_name)
+            .map_or("", |info| info.type_name()),
+        found: std::any::type_name::<N>(),
+    }
+}
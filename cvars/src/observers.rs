@@ -0,0 +1,126 @@
+use std::{collections::HashMap, fmt, sync::Arc};
+
+/// A callback called after a cvar changes.
+///
+/// The arguments are the cvar's name, its old value and its new value, all as strings.
+pub type Observer = Arc<dyn Fn(&str, &str, &str) + Send + Sync>;
+
+/// Callbacks to call when cvars change.
+///
+/// To use it, add a field of this type to your Cvars struct and mark it with `#[cvars(observers)]`.
+/// The field is not a cvar itself, it's skipped like fields marked with `#[cvars(skip)]`.
+///
+/// The generated `set`, `set_str`, `reset` and `reset_all` methods call the observers
+/// after successfully changing a cvar. Observers are only called if the value actually changed,
+/// the values are compared as strings using their `Display` impls.
+/// Changing the fields directly doesn't call the observers.
+///
+/// # Example
+///
+/// ```rust
+/// use std::sync::{Arc, Mutex};
+///
+/// use cvars::{cvars, Observers};
+///
+/// cvars! {
+///     r_vsync: bool = true,
+///     snd_volume: f32 = 1.0,
+///
+///     #[cvars(observers)]
+///     observers: Observers = Observers::new(),
+/// }
+///
+/// let mut cvars = Cvars::default();
+///
+/// let changes = Arc::new(Mutex::new(Vec::new()));
+/// let changes2 = Arc::clone(&changes);
+/// cvars.observers.add("r_vsync", move |name, old, new| {
+///     changes2.lock().unwrap().push(format!("{name}: {old} -> {new}"));
+/// });
+///
+/// cvars.set_str("r_vsync", "false").unwrap();
+/// cvars.set_str("snd_volume", "0.5").unwrap();
+/// assert_eq!(*changes.lock().unwrap(), ["r_vsync: true -> false"]);
+/// ```
+#[derive(Clone, Default)]
+pub struct Observers {
+    global: Vec<Observer>,
+    by_name: HashMap<String, Vec<Observer>>,
+}
+
+impl Observers {
+    /// Creates an empty set of observers.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers an observer which is called when the cvar named `cvar_name` changes.
+    ///
+    /// The name is not checked - if no such cvar exists, the observer is never called.
+    pub fn add<F>(&mut self, cvar_name: impl Into<String>, observer: F)
+    where
+        F: Fn(&str, &str, &str) + Send + Sync + 'static,
+    {
+        self.by_name
+            .entry(cvar_name.into())
+            .or_default()
+            .push(Arc::new(observer));
+    }
+
+    /// Registers an observer which is called when any cvar changes.
+    pub fn add_global<F>(&mut self, observer: F)
+    where
+        F: Fn(&str, &str, &str) + Send + Sync + 'static,
+    {
+        self.global.push(Arc::new(observer));
+    }
+
+    /// Removes all observers registered for the cvar named `cvar_name`.
+    ///
+    /// Global observers are not affected.
+    pub fn remove(&mut self, cvar_name: &str) {
+        self.by_name.remove(cvar_name);
+    }
+
+    /// Removes all observers, including global ones.
+    pub fn clear(&mut self) {
+        self.global.clear();
+        self.by_name.clear();
+    }
+
+    /// Returns `true` if there are no observers at all.
+    pub fn is_empty(&self) -> bool {
+        self.global.is_empty() && self.by_name.is_empty()
+    }
+
+    /// Returns `true` if any observer would be called when the cvar named `cvar_name` changes.
+    pub fn is_observed(&self, cvar_name: &str) -> bool {
+        !self.global.is_empty() || self.by_name.contains_key(cvar_name)
+    }
+
+    /// Calls the observers registered for `cvar_name` and then the global observers.
+    ///
+    /// This is called by the generated code, you normally don't need to call it yourself.
+    pub fn notify(&self, cvar_name: &str, old_value: &str, new_value: &str) {
+        if let Some(observers) = self.by_name.get(cvar_name) {
+            for observer in observers {
+                observer(cvar_name, old_value, new_value);
+            }
+        }
+        for observer in &self.global {
+            observer(cvar_name, old_value, new_value);
+        }
+    }
+}
+
+impl fmt::Debug for Observers {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Closures don't impl Debug, print at least which cvars are observed.
+        let mut names: Vec<_> = self.by_name.keys().collect();
+        names.sort();
+        f.debug_struct("Observers")
+            .field("global", &self.global.len())
+            .field("by_name", &names)
+            .finish()
+    }
+}
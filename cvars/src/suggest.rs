@@ -0,0 +1,56 @@
+use crate::CvarInfo;
+
+/// How many suggestions to return at most.
+const MAX_SUGGESTIONS: usize = 5;
+
+/// Find cvar names similar to `cvar_name`, the most similar first.
+///
+/// A name is similar if it's within a small edit distance (relative to the name's length)
+/// or if it starts with `cvar_name` (the user typed only the beginning).
+/// Ties are broken by the length of the shared prefix since cvars are usually grouped
+/// by prefixes like `g_`, `cl_` or `sv_`.
+pub(crate) fn suggest(infos: &[CvarInfo], cvar_name: &str) -> Vec<&'static str> {
+    let typed = cvar_name.to_lowercase();
+    let max_distance = typed.chars().count() / 3 + 1;
+
+    let mut candidates = Vec::new();
+    for info in infos {
+        let candidate = info.name.to_lowercase();
+        let distance = edit_distance(&typed, &candidate);
+        let is_prefix = typed.len() >= 2 && candidate.starts_with(&typed);
+        if distance <= max_distance || is_prefix {
+            let prefix_len = typed
+                .chars()
+                .zip(candidate.chars())
+                .take_while(|(a, b)| a == b)
+                .count();
+            candidates.push((distance, usize::MAX - prefix_len, info.name));
+        }
+    }
+
+    candidates.sort_unstable();
+    candidates
+        .into_iter()
+        .take(MAX_SUGGESTIONS)
+        .map(|(_, _, name)| name)
+        .collect()
+}
+
+/// Levenshtein distance - the number of inserted, removed or replaced characters.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    // Distances between the previous prefix of `a` and all prefixes of `b`.
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    let mut cur = vec![0; b.len() + 1];
+    for (i, ca) in a.chars().enumerate() {
+        cur[0] = i + 1;
+        for (j, &cb) in b.iter().enumerate() {
+            let replace = prev[j] + usize::from(ca != cb);
+            let remove = prev[j + 1] + 1;
+            let insert = cur[j] + 1;
+            cur[j + 1] = replace.min(remove).min(insert);
+        }
+        std::mem::swap(&mut prev, &mut cur);
+    }
+    prev[b.len()]
+}